@@ -2,20 +2,42 @@ from typing import (
     Optional,
     Annotated,
     Any,
-    NewType,
+    TypeVar,
     TypeVarTuple,
-    SupportsBytes,
+    Self,
+    SupportsIndex,
 )
-from types import NoneType
+from collections.abc import Buffer, Iterable, Iterator, Sequence
 from dataclasses import dataclass
+from enum import Enum, IntEnum, IntFlag
+
+from . import errors as errors
 
 Shape = TypeVarTuple("Shape")
+T = TypeVar("T")
+
+class Endianness(Enum):
+    native = 0
+    little = 1
+    big = 2
+
+# Aliases of the members of `Endianness`
+NativeEndian: Endianness
+LittleEndian: Endianness
+BigEndian: Endianness
 
-# These names are sentinel values exported from Rust
-# In order to use them as type annotations they're declared as types here
-NativeEndian = NewType("NativeEndian", NoneType)
-BigEndian = NewType("BigEndian", NoneType)
-LittleEndian = NewType("LittleEndian", NoneType)
+class Overflow(Enum):
+    error = ...
+    wrap = ...
+    saturate = ...
+
+class BitOrder(Enum):
+    lsb_first = ...
+    msb_first = ...
+
+class UnknownValue(Enum):
+    error = ...
+    keep = ...
 
 @dataclass
 class BufferLen:
@@ -23,28 +45,305 @@ class BufferLen:
 
 class Shaped:
     @classmethod
-    def __class_getitem__(cls, *params: int): ...
+    def __class_getitem__(cls, shape: int | str | tuple[int, ...]) -> type[Array]: ...
+    @classmethod
+    def from_buffer(
+        cls,
+        buffer: Buffer,
+        endianness: Optional[Endianness] = None,
+    ) -> Self: ...
+    @classmethod
+    def from_buffer_many(
+        cls,
+        buffer: Buffer,
+        endianness: Optional[Endianness] = None,
+        *,
+        strict: bool = True,
+    ) -> Records[Self]: ...
+    @classmethod
+    def iter_unpack(
+        cls,
+        buffer: Buffer,
+        endianness: Optional[Endianness] = None,
+        *,
+        strict: bool = True,
+    ) -> Iterator[Self]: ...
+    @classmethod
+    def dtype(cls, endianness: Optional[Endianness] = None) -> "numpy.dtype": ...
+    def as_numpy(self) -> "numpy.ndarray": ...
+    def __bytes__(self) -> bytes: ...
+    def __buffer__(self, flags: int) -> memoryview: ...
 
-class cstruct(Shaped[*Shape]):
-    _SHAPE: Optional[tuple[int]]
+class Records(Sequence[T]):
+    @property
+    def type(self) -> type[T]: ...
+    @property
+    def remainder(self) -> bytes: ...
+    def __len__(self) -> int: ...
+    def __getitem__(self, index: int | slice) -> Any: ...
+    def __iter__(self) -> Iterator[T]: ...
+
+class Encoding(Enum):
+    ascii = ...
+    utf8 = ...
+    latin1 = ...
+
+class Termination(Enum):
+    terminated = ...
+    padded = ...
+
+class cstr(Shaped):
+    _LENGTH: int
+    _ENCODING: Encoding
+    _TERMINATION: Termination
+    @classmethod
+    def __class_getitem__(
+        cls, options: int | tuple[int, *tuple[str | Encoding | Termination, ...]]
+    ) -> type[cstr]: ...
+    def __new__(
+        cls,
+        value: Optional[str | Buffer] = None,
+        endianness: Optional[Endianness] = None,
+    ) -> Self: ...
+    def __str__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __len__(self) -> int: ...
+    @classmethod
+    def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
+
+# `char[32]` is the same type as `cstr[32]`
+char = cstr
+
+class cstring(Shaped):
+    _ENCODING: Encoding
+    @classmethod
+    def __class_getitem__(cls, encoding: str | Encoding) -> type[cstring]: ...
+    def __new__(
+        cls,
+        value: Optional[str | Buffer] = None,
+        endianness: Optional[Endianness] = None,
+    ) -> Self: ...
+    def __str__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __len__(self) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
+
+class bytes_prefixed(Shaped):
+    _PREFIX: type[Shaped]
+    @classmethod
+    def __class_getitem__(cls, prefix: type[Shaped]) -> type[bytes_prefixed]: ...
+    def __new__(
+        cls,
+        value: Optional[Buffer] = None,
+        endianness: Optional[Endianness] = None,
+    ) -> Self: ...
+    @property
+    def data(self) -> bytes: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __len__(self) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
+
+class pascal_string(Shaped):
+    _PREFIX: type[Shaped]
+    _ENCODING: Encoding
+    @classmethod
+    def __class_getitem__(
+        cls, options: type[Shaped] | tuple[type[Shaped], str | Encoding]
+    ) -> type[pascal_string]: ...
+    def __new__(
+        cls,
+        value: Optional[str | Buffer] = None,
+        endianness: Optional[Endianness] = None,
+    ) -> Self: ...
+    def __str__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __len__(self) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
+
+class tagged(Shaped):
+    _TAG: str
+    _VARIANTS: dict[int, type[cstruct]]
+    _FALLBACK: bool
+    @classmethod
+    def __class_getitem__(
+        cls,
+        options: tuple[str, dict[int, type[cstruct]]] | tuple[str, dict[int, type[cstruct]], type[bytes]],
+    ) -> type[tagged]: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
+
+class pad(Shaped):
+    _LENGTH: int
+    @classmethod
+    def __class_getitem__(cls, length: int) -> type[pad]: ...
     def __new__(
         cls,
-        buffer: Optional[bytes] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Buffer] = None,
+        endianness: Optional[Endianness] = None,
+    ) -> Self: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
+
+class reserved(pad): ...
+
+class cenum(Shaped):
+    _BASE: type[Shaped]
+    _ENUM: type[IntEnum]
+    _UNKNOWN: UnknownValue
+    @classmethod
+    def __class_getitem__(
+        cls,
+        options: tuple[type[Shaped], type[IntEnum] | dict[str, int]]
+        | tuple[type[Shaped], type[IntEnum] | dict[str, int], UnknownValue],
+    ) -> type[cenum]: ...
+    def __new__(
+        cls,
+        value: Optional[IntEnum | str | SupportsIndex | Buffer] = None,
+        endianness: Optional[Endianness] = None,
+        overflow: Optional[Overflow] = None,
+    ) -> Self: ...
+    @property
+    def value(self) -> IntEnum | int: ...
+    def __index__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
+
+class cflags(Shaped):
+    _BASE: type[Shaped]
+    _FLAGS: type[IntFlag]
+    @classmethod
+    def __class_getitem__(
+        cls, options: tuple[type[Shaped], type[IntFlag] | dict[str, int]]
+    ) -> type[cflags]: ...
+    def __new__(
+        cls,
+        value: Optional[IntFlag | str | SupportsIndex | Iterable[IntFlag | str | SupportsIndex] | Buffer] = None,
+        endianness: Optional[Endianness] = None,
+        overflow: Optional[Overflow] = None,
+    ) -> Self: ...
+    @property
+    def flags(self) -> IntFlag: ...
+    @property
+    def residual(self) -> int: ...
+    def add(self, value: IntFlag | str | SupportsIndex | Iterable[IntFlag | str | SupportsIndex]) -> None: ...
+    def discard(self, value: IntFlag | str | SupportsIndex | Iterable[IntFlag | str | SupportsIndex]) -> None: ...
+    def __index__(self) -> int: ...
+    def __bool__(self) -> bool: ...
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[IntFlag]: ...
+    def __contains__(self, value: object) -> bool: ...
+    def __or__(self, other: object) -> Self: ...
+    def __and__(self, other: object) -> Self: ...
+    def __sub__(self, other: object) -> Self: ...
+    def __eq__(self, other: object) -> bool: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
+
+class Array(Shaped):
+    __origin__: type[Shaped]
+    _SHAPE: tuple[int, ...]
+    _COUNT: Optional[str]
+    def __new__(
+        cls,
+        value: Optional[Buffer | Sequence[Any]] = None,
+        endianness: Optional[Endianness] = None,
+        overflow: Optional[Overflow] = None,
+    ): ...
+    @property
+    def shape(self) -> tuple[int, ...]: ...
+    def __len__(self) -> int: ...
+    def __getitem__(self, index: int | slice | tuple[int | slice, ...]) -> Any: ...
+    def __setitem__(self, index: int | slice | tuple[int | slice, ...], value: Any) -> None: ...
+    def __iter__(self) -> Iterator[Any]: ...
+    @classmethod
+    def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
+
+class Layout:
+    @property
+    def size(self) -> int: ...
+    @property
+    def alignment(self) -> int: ...
+    @property
+    def dynamic(self) -> bool: ...
+    @property
+    def fields(self) -> list[tuple[str, type, int, int]]: ...
+    def __len__(self) -> int: ...
+
+class field:
+    def __init__(
+        self,
+        type_: type[Shaped],
+        *,
+        overflow: Optional[Overflow] = None,
+        endianness: Optional[Endianness] = None,
+        bits: Optional[int] = None,
+        offset: Optional[int] = None,
+        constant: Any = None,
     ): ...
+    @property
+    def type(self) -> type[Shaped]: ...
+    @property
+    def overflow(self) -> Optional[Overflow]: ...
+    @property
+    def endianness(self) -> Optional[Endianness]: ...
+    @property
+    def bits(self) -> Optional[int]: ...
+    @property
+    def offset(self) -> Optional[int]: ...
+    @property
+    def constant(self) -> Any: ...
+
+class cstruct(Shaped[*Shape]):
+    _SHAPE: Optional[tuple[int]]
+    _ALIGNMENT: int
+    _PACKED: bool
+    _ENDIANNESS: Endianness
+    _BIT_ORDER: BitOrder
+    _LAYOUT: Layout
+    def __new__(cls, *args, **kwargs): ...
     def _type_and_offset_of(self, attr: str) -> tuple[type, int]: ...
     def _offset_of(self, attr: str) -> int: ...
     def _type_of(self, attr: str) -> type: ...
     def __getattr__(self, attr: str) -> Any: ...
+    def __setattr__(self, attr: str, value: Any) -> None: ...
+    def __delattr__(self, attr: str) -> None: ...
+    @classmethod
+    def extract_column(
+        cls,
+        buffer: Buffer,
+        path: str,
+        endianness: Optional[Endianness] = None,
+        *,
+        strict: bool = True,
+    ) -> Array: ...
+    @classmethod
+    def __layout__(cls) -> Layout: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
+
+class cunion(cstruct[*Shape]): ...
 
 class boolean(Shaped[*Shape]):
     _SHAPE: Optional[tuple[int]]
     def __new__(
         cls,
-        value: Optional[Annotated[SupportsBytes, BufferLen(1)] | bool] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Annotated[Buffer, BufferLen(1)] | bool] = None,
+        endianness: Optional[Endianness] = None,
     ): ...
     def __repr__(self) -> str: ...
     def __eq__(self, other) -> bool: ...
@@ -53,15 +352,19 @@ class boolean(Shaped[*Shape]):
     def __lt__(self, other) -> bool: ...
     def __ge__(self, other) -> bool: ...
     def __gt__(self, other) -> bool: ...
+    def __bool__(self) -> bool: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
 
 class i8(Shaped[*Shape]):
     _SHAPE: Optional[tuple[int]]
     def __new__(
         cls,
-        value: Optional[Annotated[SupportsBytes, BufferLen(1)] | int] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Annotated[Buffer, BufferLen(1)] | int] = None,
+        endianness: Optional[Endianness] = None,
+        overflow: Optional[Overflow] = None,
     ): ...
     def __repr__(self) -> str: ...
     def __eq__(self, other) -> bool: ...
@@ -70,15 +373,19 @@ class i8(Shaped[*Shape]):
     def __lt__(self, other) -> bool: ...
     def __ge__(self, other) -> bool: ...
     def __gt__(self, other) -> bool: ...
+    def __index__(self) -> int: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
 
 class i16(Shaped[*Shape]):
     _SHAPE: Optional[tuple[int]]
     def __new__(
         cls,
-        value: Optional[Annotated[SupportsBytes, BufferLen(2)] | int] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Annotated[Buffer, BufferLen(2)] | int] = None,
+        endianness: Optional[Endianness] = None,
+        overflow: Optional[Overflow] = None,
     ): ...
     def __repr__(self) -> str: ...
     def __eq__(self, other) -> bool: ...
@@ -87,15 +394,19 @@ class i16(Shaped[*Shape]):
     def __lt__(self, other) -> bool: ...
     def __ge__(self, other) -> bool: ...
     def __gt__(self, other) -> bool: ...
+    def __index__(self) -> int: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
 
 class i32(Shaped[*Shape]):
     _SHAPE: Optional[tuple[int]]
     def __new__(
         cls,
-        value: Optional[Annotated[SupportsBytes, BufferLen(4)] | int] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Annotated[Buffer, BufferLen(4)] | int] = None,
+        endianness: Optional[Endianness] = None,
+        overflow: Optional[Overflow] = None,
     ): ...
     def __repr__(self) -> str: ...
     def __eq__(self, other) -> bool: ...
@@ -104,15 +415,19 @@ class i32(Shaped[*Shape]):
     def __lt__(self, other) -> bool: ...
     def __ge__(self, other) -> bool: ...
     def __gt__(self, other) -> bool: ...
+    def __index__(self) -> int: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
 
 class i64(Shaped[*Shape]):
     _SHAPE: Optional[tuple[int]]
     def __new__(
         cls,
-        value: Optional[Annotated[SupportsBytes, BufferLen(8)] | int] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Annotated[Buffer, BufferLen(8)] | int] = None,
+        endianness: Optional[Endianness] = None,
+        overflow: Optional[Overflow] = None,
     ): ...
     def __repr__(self) -> str: ...
     def __eq__(self, other) -> bool: ...
@@ -121,15 +436,19 @@ class i64(Shaped[*Shape]):
     def __lt__(self, other) -> bool: ...
     def __ge__(self, other) -> bool: ...
     def __gt__(self, other) -> bool: ...
+    def __index__(self) -> int: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
 
 class i128(Shaped[*Shape]):
     _SHAPE: Optional[tuple[int]]
     def __new__(
         cls,
-        value: Optional[Annotated[SupportsBytes, BufferLen(16)] | int] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Annotated[Buffer, BufferLen(16)] | int] = None,
+        endianness: Optional[Endianness] = None,
+        overflow: Optional[Overflow] = None,
     ): ...
     def __repr__(self) -> str: ...
     def __eq__(self, other) -> bool: ...
@@ -138,15 +457,19 @@ class i128(Shaped[*Shape]):
     def __lt__(self, other) -> bool: ...
     def __ge__(self, other) -> bool: ...
     def __gt__(self, other) -> bool: ...
+    def __index__(self) -> int: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
 
 class u8(Shaped[*Shape]):
     _SHAPE: Optional[tuple[int]]
     def __new__(
         cls,
-        value: Optional[Annotated[SupportsBytes, BufferLen(1)] | int] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Annotated[Buffer, BufferLen(1)] | int] = None,
+        endianness: Optional[Endianness] = None,
+        overflow: Optional[Overflow] = None,
     ): ...
     def __repr__(self) -> str: ...
     def __eq__(self, other) -> bool: ...
@@ -155,15 +478,19 @@ class u8(Shaped[*Shape]):
     def __lt__(self, other) -> bool: ...
     def __ge__(self, other) -> bool: ...
     def __gt__(self, other) -> bool: ...
+    def __index__(self) -> int: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
 
 class u16(Shaped[*Shape]):
     _SHAPE: Optional[tuple[int]]
     def __new__(
         cls,
-        value: Optional[Annotated[SupportsBytes, BufferLen(2)] | int] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Annotated[Buffer, BufferLen(2)] | int] = None,
+        endianness: Optional[Endianness] = None,
+        overflow: Optional[Overflow] = None,
     ): ...
     def __repr__(self) -> str: ...
     def __eq__(self, other) -> bool: ...
@@ -172,15 +499,19 @@ class u16(Shaped[*Shape]):
     def __lt__(self, other) -> bool: ...
     def __ge__(self, other) -> bool: ...
     def __gt__(self, other) -> bool: ...
+    def __index__(self) -> int: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
 
 class u32(Shaped[*Shape]):
     _SHAPE: Optional[tuple[int]]
     def __new__(
         cls,
-        value: Optional[Annotated[SupportsBytes, BufferLen(4)] | int] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Annotated[Buffer, BufferLen(4)] | int] = None,
+        endianness: Optional[Endianness] = None,
+        overflow: Optional[Overflow] = None,
     ): ...
     def __repr__(self) -> str: ...
     def __eq__(self, other) -> bool: ...
@@ -189,15 +520,19 @@ class u32(Shaped[*Shape]):
     def __lt__(self, other) -> bool: ...
     def __ge__(self, other) -> bool: ...
     def __gt__(self, other) -> bool: ...
+    def __index__(self) -> int: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
 
 class u64(Shaped[*Shape]):
     _SHAPE: Optional[tuple[int]]
     def __new__(
         cls,
-        value: Optional[Annotated[SupportsBytes, BufferLen(8)] | int] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Annotated[Buffer, BufferLen(8)] | int] = None,
+        endianness: Optional[Endianness] = None,
+        overflow: Optional[Overflow] = None,
     ): ...
     def __repr__(self) -> str: ...
     def __eq__(self, other) -> bool: ...
@@ -206,15 +541,19 @@ class u64(Shaped[*Shape]):
     def __lt__(self, other) -> bool: ...
     def __ge__(self, other) -> bool: ...
     def __gt__(self, other) -> bool: ...
+    def __index__(self) -> int: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
 
 class u128(Shaped[*Shape]):
     _SHAPE: Optional[tuple[int]]
     def __new__(
         cls,
-        value: Optional[Annotated[SupportsBytes, BufferLen(16)] | int] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Annotated[Buffer, BufferLen(16)] | int] = None,
+        endianness: Optional[Endianness] = None,
+        overflow: Optional[Overflow] = None,
     ): ...
     def __repr__(self) -> str: ...
     def __eq__(self, other) -> bool: ...
@@ -223,15 +562,18 @@ class u128(Shaped[*Shape]):
     def __lt__(self, other) -> bool: ...
     def __ge__(self, other) -> bool: ...
     def __gt__(self, other) -> bool: ...
+    def __index__(self) -> int: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
 
 class f16(Shaped[*Shape]):
     _SHAPE: Optional[tuple[int]]
     def __new__(
         cls,
-        value: Optional[Annotated[SupportsBytes, BufferLen(2)] | float] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Annotated[Buffer, BufferLen(2)] | float] = None,
+        endianness: Optional[Endianness] = None,
     ): ...
     def __repr__(self) -> str: ...
     def __eq__(self, other) -> bool: ...
@@ -240,15 +582,18 @@ class f16(Shaped[*Shape]):
     def __lt__(self, other) -> bool: ...
     def __ge__(self, other) -> bool: ...
     def __gt__(self, other) -> bool: ...
+    def __float__(self) -> float: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
 
 class f32(Shaped[*Shape]):
     _SHAPE: Optional[tuple[int]]
     def __new__(
         cls,
-        value: Optional[Annotated[SupportsBytes, BufferLen(4)] | float] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Annotated[Buffer, BufferLen(4)] | float] = None,
+        endianness: Optional[Endianness] = None,
     ): ...
     def __repr__(self) -> str: ...
     def __eq__(self, other) -> bool: ...
@@ -257,15 +602,18 @@ class f32(Shaped[*Shape]):
     def __lt__(self, other) -> bool: ...
     def __ge__(self, other) -> bool: ...
     def __gt__(self, other) -> bool: ...
+    def __float__(self) -> float: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
 
 class f64(Shaped[*Shape]):
     _SHAPE: Optional[tuple[int]]
     def __new__(
         cls,
-        value: Optional[Annotated[SupportsBytes, BufferLen(8)] | float] = None,
-        endianness: Optional[NativeEndian | BigEndian | LittleEndian] = None,
+        value: Optional[Annotated[Buffer, BufferLen(8)] | float] = None,
+        endianness: Optional[Endianness] = None,
     ): ...
     def __repr__(self) -> str: ...
     def __eq__(self, other) -> bool: ...
@@ -274,5 +622,8 @@ class f64(Shaped[*Shape]):
     def __lt__(self, other) -> bool: ...
     def __ge__(self, other) -> bool: ...
     def __gt__(self, other) -> bool: ...
+    def __float__(self) -> float: ...
     @classmethod
     def __packed_size__(cls) -> int: ...
+    @classmethod
+    def __alignment__(cls) -> int: ...
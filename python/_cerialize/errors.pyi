@@ -0,0 +1,7 @@
+class CerializeError(Exception): ...
+class BufferSizeError(CerializeError, ValueError): ...
+class LayoutError(CerializeError, TypeError): ...
+class ValueRangeError(CerializeError, OverflowError): ...
+class ShapeError(CerializeError, ValueError): ...
+class UnknownTagError(CerializeError, ValueError): ...
+class ConstantMismatchError(CerializeError, ValueError): ...
@@ -13,9 +13,9 @@ class SupportsBool(Protocol):
 
 
 class bool(Generic[*Shape], _cerialize._bool):
-    def __new__(cls, *args) -> "bool":
+    def __new__(cls, *args, **kwargs) -> "bool":
         value = next(iter(args), builtins.bool()).__bool__()
-        return super().__new__(cls, value)
+        return super().__new__(cls, value, *args[1:], **kwargs)
 
     @overload
     def __eq__(self, value: builtins.bool) -> builtins.bool:
@@ -115,9 +115,9 @@ class bool(Generic[*Shape], _cerialize._bool):
 
 
 class i8(Generic[*Shape], _cerialize._i8):
-    def __new__(cls, *args) -> "i8":
+    def __new__(cls, *args, **kwargs) -> "i8":
         value = next(iter(args), int())
-        return super().__new__(cls, value)
+        return super().__new__(cls, value, *args[1:], **kwargs)
 
     @overload
     def __eq__(self, value: "i8") -> builtins.bool:
@@ -213,9 +213,9 @@ class i8(Generic[*Shape], _cerialize._i8):
 
 
 class i16(Generic[*Shape], _cerialize._i16):
-    def __new__(cls, *args) -> "i16":
+    def __new__(cls, *args, **kwargs) -> "i16":
         value = next(iter(args), int())
-        return super().__new__(cls, value)
+        return super().__new__(cls, value, *args[1:], **kwargs)
 
     @overload
     def __eq__(self, value: "i16") -> builtins.bool:
@@ -311,9 +311,9 @@ class i16(Generic[*Shape], _cerialize._i16):
 
 
 class i32(Generic[*Shape], _cerialize._i32):
-    def __new__(cls, *args) -> "i32":
+    def __new__(cls, *args, **kwargs) -> "i32":
         value = next(iter(args), int())
-        return super().__new__(cls, value)
+        return super().__new__(cls, value, *args[1:], **kwargs)
 
     @overload
     def __eq__(self, value: "i32") -> builtins.bool:
@@ -409,9 +409,9 @@ class i32(Generic[*Shape], _cerialize._i32):
 
 
 class i64(Generic[*Shape], _cerialize._i64):
-    def __new__(cls, *args) -> "i64":
+    def __new__(cls, *args, **kwargs) -> "i64":
         value = next(iter(args), int())
-        return super().__new__(cls, value)
+        return super().__new__(cls, value, *args[1:], **kwargs)
 
     @overload
     def __eq__(self, value: "i64") -> builtins.bool:
@@ -507,9 +507,9 @@ class i64(Generic[*Shape], _cerialize._i64):
 
 
 class u8(Generic[*Shape], _cerialize._u8):
-    def __new__(cls, *args) -> "u8":
+    def __new__(cls, *args, **kwargs) -> "u8":
         value = next(iter(args), int())
-        return super().__new__(cls, value)
+        return super().__new__(cls, value, *args[1:], **kwargs)
 
     @overload
     def __eq__(self, value: "u8") -> builtins.bool:
@@ -605,9 +605,9 @@ class u8(Generic[*Shape], _cerialize._u8):
 
 
 class u16(Generic[*Shape], _cerialize._u16):
-    def __new__(cls, *args) -> "u16":
+    def __new__(cls, *args, **kwargs) -> "u16":
         value = next(iter(args), int())
-        return super().__new__(cls, value)
+        return super().__new__(cls, value, *args[1:], **kwargs)
 
     @overload
     def __eq__(self, value: "u16") -> builtins.bool:
@@ -703,9 +703,9 @@ class u16(Generic[*Shape], _cerialize._u16):
 
 
 class u32(Generic[*Shape], _cerialize._u32):
-    def __new__(cls, *args) -> "u32":
+    def __new__(cls, *args, **kwargs) -> "u32":
         value = next(iter(args), int())
-        return super().__new__(cls, value)
+        return super().__new__(cls, value, *args[1:], **kwargs)
 
     @overload
     def __eq__(self, value: "u32") -> builtins.bool:
@@ -801,9 +801,9 @@ class u32(Generic[*Shape], _cerialize._u32):
 
 
 class u64(Generic[*Shape], _cerialize._u64):
-    def __new__(cls, *args) -> "u64":
+    def __new__(cls, *args, **kwargs) -> "u64":
         value = next(iter(args), int())
-        return super().__new__(cls, value)
+        return super().__new__(cls, value, *args[1:], **kwargs)
 
     @overload
     def __eq__(self, value: "u64") -> builtins.bool:
@@ -899,9 +899,9 @@ class u64(Generic[*Shape], _cerialize._u64):
 
 
 class f16(Generic[*Shape], _cerialize._f16):
-    def __new__(cls, *args) -> "f16":
+    def __new__(cls, *args, **kwargs) -> "f16":
         value = next(iter(args), float())
-        return super().__new__(cls, value)
+        return super().__new__(cls, value, *args[1:], **kwargs)
 
     @overload
     def __eq__(self, value: "f16") -> builtins.bool:
@@ -997,9 +997,9 @@ class f16(Generic[*Shape], _cerialize._f16):
 
 
 class f32(Generic[*Shape], _cerialize._f32):
-    def __new__(cls, *args) -> "f32":
+    def __new__(cls, *args, **kwargs) -> "f32":
         value = next(iter(args), float())
-        return super().__new__(cls, value)
+        return super().__new__(cls, value, *args[1:], **kwargs)
 
     @overload
     def __eq__(self, value: "f32") -> builtins.bool:
@@ -1095,9 +1095,9 @@ class f32(Generic[*Shape], _cerialize._f32):
 
 
 class f64(Generic[*Shape], _cerialize._f64):
-    def __new__(cls, *args) -> "f64":
+    def __new__(cls, *args, **kwargs) -> "f64":
         value = next(iter(args), float())
-        return super().__new__(cls, value)
+        return super().__new__(cls, value, *args[1:], **kwargs)
 
     @overload
     def __eq__(self, value: "f64") -> builtins.bool:
@@ -1,11 +1,11 @@
-from enum import Enum
-from typing import _GenericAlias, Any, Generic, TypeVarTuple
+from typing import _GenericAlias, Annotated, Any, Generic, TypeVarTuple, get_args, get_origin
 from types import NoneType, new_class
 from functools import lru_cache
-from math import prod
 from dataclasses import dataclass
 
+import _cerialize
 import cerialize.base_types as basic
+from cerialize.errors import LayoutError, ShapeError
 
 Shape = TypeVarTuple("Shape")
 
@@ -20,10 +20,28 @@ class _func_arg:
     type: type
     prefix: str = ""
 
-class endianness(Enum):
-    native = 0
-    little = 1
-    big = 2
+# The byte order is shared with `_cerialize` so that the decorator's argument can be handed to it as is
+endianness = _cerialize.Endianness
+bit_order = _cerialize.BitOrder
+
+@dataclass(eq=True, frozen=True)
+class bits:
+    """The width of a bitfield, e.g. `Annotated[u32, bits(3)]` for `uint32_t flags : 3;`"""
+    width: int
+
+@dataclass(eq=True, frozen=True)
+class offset:
+    """Where a field starts within its struct, e.g. `Annotated[u16, offset(0x40)]` for a field documented at 0x40"""
+    value: int
+
+@dataclass(eq=True, frozen=True)
+class constant:
+    """The value a field always holds, e.g. `Annotated[u32, constant(0xCAFEBABE)]` for a magic number which is checked when decoding"""
+    value: Any
+
+def _is_padding(_type: Any) -> bool:
+    # `pad` and `reserved` fields only take up space, so they are left out of `__init__` and `__repr__`
+    return isinstance(_type, type) and issubclass(_type, _cerialize.pad)
 
 def _get_properties(cls: type) -> dict[str, Any]:
     if isinstance(cls, _GenericAlias):
@@ -73,8 +91,14 @@ def _define_init(cls: type, *args: _func_arg, body: list[str] | None = None) ->
     setattr(cls, "__init__", init_fn)
 
 def _define_repr(cls: type) -> None:
+    # Flag sets are shown by the names of their flags, e.g. `mode=read|write`, rather than by their type
+    def conversion(attr: str) -> str:
+        _type = cls.__annotations__.get(attr)
+        return "s" if isinstance(_type, type) and issubclass(_type, _cerialize.cflags) else "r"
+
+    shown = [attr for attr in cls._CFIELDS.keys() if not _is_padding(cls.__annotations__.get(attr))]
     body = [
-        f"return self.__class__.__qualname__ + f'("+ ', '.join([f"{attr}={{self.{attr}!r}}" for attr in cls._CFIELDS.keys()]) + ")'"
+        f"return self.__class__.__qualname__ + f'("+ ', '.join([f"{attr}={{self.{attr}!{conversion(attr)}}}" for attr in shown]) + ")'"
     ]
     repr_fn = _create_fn("__repr__", [_func_arg(name="self", type=cls)], str, body)
     repr_fn.__qualname__ = f"{cls.__qualname__}.{repr_fn.__name__}"
@@ -99,17 +123,93 @@ def _supported_type(cls: type) -> bool:
     if cls in __baseline_types:
         return True
 
+    # The `_cerialize` primitives are supported on their own as well, e.g. in the variants of a `tagged` union
+    if isinstance(cls, type) and issubclass(cls, (
+        _cerialize.boolean,
+        _cerialize.i8,
+        _cerialize.i16,
+        _cerialize.i32,
+        _cerialize.i64,
+        _cerialize.u8,
+        _cerialize.u16,
+        _cerialize.u32,
+        _cerialize.u64,
+        _cerialize.f16,
+        _cerialize.f32,
+        _cerialize.f64,
+    )):
+        return True
+
+    # Strings are supported once they have a length, e.g. `cstr[32]`
+    if isinstance(cls, type) and issubclass(cls, _cerialize.cstr):
+        return getattr(cls, "_LENGTH", None) is not None
+
+    # Null-terminated strings are dynamically sized, which `_cerialize.cstruct` lays out per instance
+    if isinstance(cls, type) and issubclass(cls, _cerialize.cstring):
+        return True
+
+    # Length-prefixed types are dynamically sized as well, once they have a prefix, e.g. `bytes_prefixed[u32]`
+    if isinstance(cls, type) and issubclass(cls, (_cerialize.bytes_prefixed, _cerialize.pascal_string)):
+        return getattr(cls, "_PREFIX", None) is not None
+
+    # Padding is supported once it has a length, e.g. `pad[4]` or `reserved[8]`
+    if isinstance(cls, type) and issubclass(cls, _cerialize.pad):
+        return getattr(cls, "_LENGTH", None) is not None
+
+    # Enums are supported once they have an integer type and names, e.g. `cenum[u8, Status]`
+    if isinstance(cls, type) and issubclass(cls, _cerialize.cenum):
+        return getattr(cls, "_BASE", None) is not None
+
+    # Flag sets are supported once they have an integer type and flags, e.g. `cflags[u8, Permissions]`
+    if isinstance(cls, type) and issubclass(cls, _cerialize.cflags):
+        return getattr(cls, "_BASE", None) is not None
+
+    # Tagged unions are supported once they have variants, each of which has to be supported itself
+    if isinstance(cls, type) and issubclass(cls, _cerialize.tagged):
+        variants = getattr(cls, "_VARIANTS", None)
+        return variants is not None and all(_supported_type(variant) for variant in variants.values())
+
+    # Arrays are supported whenever their elements are
+    if isinstance(cls, type) and issubclass(cls, _cerialize.Array):
+        return _supported_type(cls.__origin__)
+
     fields: dict[str, _type_spesification] | None = cls.__dict__.get("_CFIELDS")
 
     # I don't think checking if this is None is the best idea, but it seems to fix an issue where the `_CFIELDS` class attribute is overwritten for some reason
     if fields is not None:
         # Check that all the internal fields are supported
-        return all(_supported_type(v) for v in fields.values())
+        return all(
+            _supported_type(v.type if isinstance(v, _cerialize.field) else v)
+            for v in fields.values()
+        )
 
-    breakpoint()
     return False
 
 
+def _field_options(annotation: Any) -> tuple[type, dict[str, Any]]:
+    # Options are attached to a field's type through `Annotated`, e.g. `Annotated[u8, Overflow.wrap]` or `Annotated[u16, BigEndian]`
+    if get_origin(annotation) is not Annotated:
+        return annotation, {}
+
+    _type, *metadata = get_args(annotation)
+    options: dict[str, Any] = {}
+    for option in metadata:
+        match option:
+            case _cerialize.Overflow():
+                options["overflow"] = option
+            case _cerialize.Endianness():
+                options["endianness"] = option
+            case bits(width=width):
+                options["bits"] = width
+            case offset(value=value):
+                options["offset"] = value
+            case constant(value=value):
+                options["constant"] = value
+            case _:
+                raise LayoutError(f"Unsupported field option {option!r} for {_type!r}")
+
+    return _type, options
+
 def _determine_type(cls: type, shape: tuple[int, ...] | None = None) -> _type_spesification:
     # Check if type has nested arguments
     match cls.__dict__.get("__args__"):
@@ -119,11 +219,11 @@ def _determine_type(cls: type, shape: tuple[int, ...] | None = None) -> _type_sp
         case [*vals] if all(isinstance(x, int) for x in vals):
             # The type has some dimensions that need to be considered
             if shape is not None:
-                raise ValueError(f"Attempt to override assigned shape")
+                raise ShapeError(f"Attempt to override the assigned shape of {cls!r}")
             return _determine_type(cls.__origin__, tuple(vals))
         case _:
             # Uh oh... Something has gone wrong
-            raise NotImplementedError(f"Unable to determine support for type {cls!r}")
+            raise LayoutError(f"Unable to determine support for type {cls!r}")
 
 @lru_cache
 def _resolve_type(spec: _type_spesification) -> type:
@@ -133,29 +233,10 @@ def _resolve_type(spec: _type_spesification) -> type:
         case (1,):
             return spec.base
         case [*shape]:
-            # Create a new type which is based on the base type but with `prod(*shape)` elements
-            new_type = type(f"{spec.base.__name__}[{','.join(f'{x!s}' for x in shape)}]",
-                            (spec.base,),
-                            {
-                                "__module__": spec.base.__module__,
-                            }
-            )
-
-            # The new type's constructor will accept one item or `prod(*shape)` items
-            # If only one item is provided, that value is assigned to every index
-            # If `prod(*shape)` items are provided, each index gets its own value
-            init_body = [
-                f"match args:",
-                f"  case (value,):",
-                f"    self._data = [value] * {prod(shape)}",
-                f"  case [*values] if len(values) == {prod(shape)}:",
-                f"    self._data = list(*values)",
-                f"  case _:",
-                f"    raise Exception('Invalid number of arguments')",
-            ]
-            init_args = [_func_arg(name="args", type=spec.base, prefix="*")]
-            _define_init(new_type, init_args, body=init_body)
-            return new_type
+            # Subscripting a shaped type creates the array type (`_cerialize.Array`) which holds `prod(*shape)` elements.
+            # `Generic` precedes the `_cerialize` base of the declared types (e.g. `cerialize.u8`), whose subscription would create a typing alias instead
+            owner = next(base for base in spec.base.__mro__ if base is not Generic and "__class_getitem__" in vars(base))
+            return vars(owner)["__class_getitem__"].__get__(None, spec.base)(tuple(shape))
         case _:
             # Uh oh... Something has gone wrong
             raise NotImplementedError(f"Unable to resolve type for {spec!s}")
@@ -163,9 +244,11 @@ def _resolve_type(spec: _type_spesification) -> type:
 
 def _process_class(
     cls: type,
+    base: type,
     generate_init: bool,
     generate_repr: bool,
     endianness: endianness,
+    bit_order: bit_order,
     alignment: int,
     packed: bool,
     serialize: bool,
@@ -188,10 +271,17 @@ def _process_class(
         "__weakref__",
         # Cerialized class attributes
         "_CFIELDS",
+        "_ALIGNMENT",
+        "_PACKED",
+        "_ENDIANNESS",
+        "_BIT_ORDER",
+        "_LAYOUT",
     }
 
     # Dictionaries have ordered insertion which comes to play here and does have an effect on the fields themselves
     fields: dict[str, _type_spesification] = {}
+    # Fields with options are declared as `_cerialize.field`s instead of plain types
+    declared_fields: dict[str, Any] = {}
     annotations = cls.__dict__.get("__annotations__", {})
 
     # Figure out if there are any fields which aren't type annotated properly
@@ -199,38 +289,61 @@ def _process_class(
         if name in __ignored_attributes:
             continue
         elif name not in annotations and not isinstance(value, (type, _GenericAlias)):
-            breakpoint()
-            raise TypeError(f"Field {name!r} in {cls!r} is missing a type annotation")
+            raise LayoutError(f"Field {name!r} in {cls!r} is missing a type annotation")
+
 
+    constants: set[str] = set()
 
     # Check if the annotation is supported
-    for name, _type in annotations.items():
+    for name, annotation in annotations.items():
+        _type, options = _field_options(annotation)
+        if "constant" in options:
+            constants.add(name)
         _type_spec = _determine_type(_type)
         if not _supported_type(_type_spec.base):
-            breakpoint()
-            raise TypeError(
+            raise LayoutError(
                 f"Field {name!r} in {cls!r} is annotated with an unsupported type"
             )
         else:
             fields.update({name: _resolve_type(_type_spec)})
+            declared_fields.update({name: _cerialize.field(fields[name], **options) if options else fields[name]})
+
+    if alignment < 1 or alignment & (alignment - 1) != 0:
+        raise LayoutError(f"The alignment of {cls!r} must be a power of two, not {alignment}")
 
-    new_type = new_class(cls.__name__, (cls, Generic[*Shape]))
+    # The layout itself (offsets, padding, size) is computed by `_cerialize.cstruct` from these attributes
+    new_type = new_class(cls.__name__, (cls, base, Generic[*Shape]))
     setattr(new_type, "__module__", cls.__module__)
     setattr(new_type, "__annotations__", fields)
-    setattr(new_type, "_CFIELDS", fields)
+    setattr(new_type, "_CFIELDS", declared_fields)
+    setattr(new_type, "_ALIGNMENT", alignment)
+    setattr(new_type, "_PACKED", packed)
+    setattr(new_type, "_ENDIANNESS", endianness)
+    setattr(new_type, "_BIT_ORDER", bit_order)
 
     # TODO: Figure out which fields have initializers
-    initialized: set[str] = set()
-
-    # TODO: Generate getters and delete setters for constant fields
+    # Padding and constants aren't passed to `__init__`, new instances already hold their contents
+    initialized: set[str] = {name for name, _type in fields.items() if _is_padding(_type)} | constants
 
     # Generate an `__init__` function if `generate_init` is set and it isn't already defined
     if generate_init and "__init__" not in cls.__dict__:
-        _define_init(new_type, (_func_arg(name=name, type=_type) for name, _type in fields.items() if name not in initialized))
+        if issubclass(base, _cerialize.cunion):
+            # The members of a union overlap, so only the ones which are given (usually a single one) are assigned
+            _define_init(new_type, [_func_arg(name="members", type=Any, prefix="**")], body=[
+                "for name, value in members.items():",
+                "  if name not in self._CFIELDS:",
+                "    raise TypeError(f'{self.__class__.__qualname__} has no member {name!r}')",
+                "  setattr(self, name, value)",
+            ])
+        else:
+            _define_init(new_type, (_func_arg(name=name, type=_type) for name, _type in fields.items() if name not in initialized))
 
     if generate_repr and "__repr__" not in cls.__dict__:
         _define_repr(new_type)
 
+    # Compile the layout up front so that attribute access doesn't have to and so that layout errors surface here
+    new_type.__layout__()
+
     return new_type
 
 
@@ -241,6 +354,7 @@ def cstruct(
     init: bool = True,
     repr: bool = True,
     endianness: endianness = endianness.native,
+    bit_order: bit_order = bit_order.lsb_first,
     alignment: int = 1,
     packed: bool = False,
     serialize: bool = False,
@@ -252,8 +366,35 @@ def cstruct(
 
     def wrap(cls):
         return _process_class(
-            cls, init, repr, endianness, alignment, packed, serialize, deserialize
+            cls, _cerialize.cstruct, init, repr, endianness, bit_order, alignment, packed, serialize, deserialize
         )
 
     # Allows for use by both @cstruct and cstruct()
     return wrap if cls is None else wrap(cls)
+
+
+def cunion(
+    cls: type | None = None,
+    /,
+    *,
+    init: bool = True,
+    repr: bool = True,
+    endianness: endianness = endianness.native,
+    bit_order: bit_order = bit_order.lsb_first,
+    alignment: int = 1,
+    packed: bool = False,
+    serialize: bool = False,
+    deserialize: bool = False,
+):
+    """
+    The same as `cstruct`, except that every member starts at the beginning of the union.
+    The generated `__init__` takes the members to assign as keyword arguments.
+    """
+
+    def wrap(cls):
+        return _process_class(
+            cls, _cerialize.cunion, init, repr, endianness, bit_order, alignment, packed, serialize, deserialize
+        )
+
+    # Allows for use by both @cunion and cunion()
+    return wrap if cls is None else wrap(cls)
@@ -0,0 +1,20 @@
+# The exceptions are defined in Rust so that they can be raised from there as well as from the decorator
+from _cerialize.errors import (
+    CerializeError,
+    BufferSizeError,
+    LayoutError,
+    ValueRangeError,
+    ShapeError,
+    UnknownTagError,
+    ConstantMismatchError,
+)
+
+__all__ = [
+    "CerializeError",
+    "BufferSizeError",
+    "LayoutError",
+    "ValueRangeError",
+    "ShapeError",
+    "UnknownTagError",
+    "ConstantMismatchError",
+]
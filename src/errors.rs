@@ -0,0 +1,153 @@
+use pyo3::exceptions::{PyException, PyOverflowError, PyTypeError, PyValueError};
+use pyo3::once_cell::GILOnceCell;
+use pyo3::prelude::*;
+use pyo3::types::{PyTuple, PyType};
+use pyo3::{ffi, AsPyPointer, PyErrArguments};
+
+use std::ffi::CString;
+
+/// An exception type which is created the first time it is needed.
+/// PyO3's `create_exception!` only supports a single base class, but every error here also derives from the builtin exception it refines so existing `except ValueError:` handlers keep working.
+pub struct ErrorType {
+    name: &'static str,
+    doc: &'static str,
+    bases: for<'py> fn(Python<'py>) -> Vec<&'py PyType>,
+    type_object: GILOnceCell<Py<PyType>>,
+}
+
+impl ErrorType {
+    const fn new(
+        name: &'static str,
+        doc: &'static str,
+        bases: for<'py> fn(Python<'py>) -> Vec<&'py PyType>,
+    ) -> Self {
+        Self {
+            name,
+            doc,
+            bases,
+            type_object: GILOnceCell::new(),
+        }
+    }
+
+    pub fn type_object<'py>(&'static self, py: Python<'py>) -> &'py PyType {
+        self.type_object
+            .get_or_init(py, || {
+                self.create(py)
+                    .unwrap_or_else(|err| panic!("Failed to create {}: {err}", self.name))
+            })
+            .as_ref(py)
+    }
+
+    pub fn new_err<A>(&'static self, args: A) -> PyErr
+    where
+        A: PyErrArguments + Send + Sync + 'static,
+    {
+        Python::with_gil(|py| PyErr::from_type(self.type_object(py), args))
+    }
+
+    fn create(&self, py: Python<'_>) -> PyResult<Py<PyType>> {
+        let name = CString::new(format!("cerialize.errors.{}", self.name))?;
+        let doc = CString::new(self.doc)?;
+        let bases = PyTuple::new(py, (self.bases)(py));
+
+        unsafe {
+            Py::from_owned_ptr_or_err(
+                py,
+                ffi::PyErr_NewExceptionWithDoc(
+                    name.as_ptr(),
+                    doc.as_ptr(),
+                    bases.as_ptr(),
+                    std::ptr::null_mut(),
+                ),
+            )
+        }
+    }
+}
+
+pub static CERIALIZE_ERROR: ErrorType = ErrorType::new(
+    "CerializeError",
+    "The base class of every error raised by cerialize",
+    |py| vec![py.get_type::<PyException>()],
+);
+
+pub static BUFFER_SIZE_ERROR: ErrorType = ErrorType::new(
+    "BufferSizeError",
+    "Raised when a buffer doesn't have the number of bytes required by a type",
+    |py| {
+        vec![
+            CERIALIZE_ERROR.type_object(py),
+            py.get_type::<PyValueError>(),
+        ]
+    },
+);
+
+pub static LAYOUT_ERROR: ErrorType = ErrorType::new(
+    "LayoutError",
+    "Raised when the layout of a struct can't be computed from its declaration",
+    |py| {
+        vec![
+            CERIALIZE_ERROR.type_object(py),
+            py.get_type::<PyTypeError>(),
+        ]
+    },
+);
+
+pub static VALUE_RANGE_ERROR: ErrorType = ErrorType::new(
+    "ValueRangeError",
+    "Raised when a value can't be represented by the type it is assigned to",
+    |py| {
+        vec![
+            CERIALIZE_ERROR.type_object(py),
+            py.get_type::<PyOverflowError>(),
+        ]
+    },
+);
+
+pub static SHAPE_ERROR: ErrorType = ErrorType::new(
+    "ShapeError",
+    "Raised when the shape of an array is invalid or doesn't match the value assigned to it",
+    |py| {
+        vec![
+            CERIALIZE_ERROR.type_object(py),
+            py.get_type::<PyValueError>(),
+        ]
+    },
+);
+
+pub static UNKNOWN_TAG_ERROR: ErrorType = ErrorType::new(
+    "UnknownTagError",
+    "Raised when the tag of a tagged union doesn't select any of its variants",
+    |py| {
+        vec![
+            CERIALIZE_ERROR.type_object(py),
+            py.get_type::<PyValueError>(),
+        ]
+    },
+);
+
+pub static CONSTANT_MISMATCH_ERROR: ErrorType = ErrorType::new(
+    "ConstantMismatchError",
+    "Raised when a constant field (e.g. a magic number) doesn't hold the value it is declared with",
+    |py| {
+        vec![
+            CERIALIZE_ERROR.type_object(py),
+            py.get_type::<PyValueError>(),
+        ]
+    },
+);
+
+/// Adds every error type to `module`
+pub fn register(py: Python<'_>, module: &PyModule) -> PyResult<()> {
+    for error in [
+        &CERIALIZE_ERROR,
+        &BUFFER_SIZE_ERROR,
+        &LAYOUT_ERROR,
+        &VALUE_RANGE_ERROR,
+        &SHAPE_ERROR,
+        &UNKNOWN_TAG_ERROR,
+        &CONSTANT_MISMATCH_ERROR,
+    ] {
+        module.add(error.name, error.type_object(py))?;
+    }
+    Ok(())
+}
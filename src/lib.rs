@@ -1,3 +1,4 @@
+mod errors;
 mod types;
 
 use pyo3::prelude::*;
@@ -5,7 +6,7 @@ use pyo3::prelude::*;
 /// A Python module implemented in Rust.
 #[pymodule]
 #[pyo3(name = "_cerialize")]
-fn cerialize(_py: Python, m: &PyModule) -> PyResult<()> {
+fn cerialize(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<types::Bool>()?;
     m.add_class::<types::Int8>()?;
     m.add_class::<types::Int16>()?;
@@ -20,12 +21,43 @@ fn cerialize(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<types::Float16>()?;
     m.add_class::<types::Float32>()?;
     m.add_class::<types::Float64>()?;
+    m.add_class::<types::CStr>()?;
+    // `char[32]` reads the same as the C declaration it mirrors
+    m.add("char", py.get_type::<types::CStr>())?;
+    m.add_class::<types::CString>()?;
+    m.add_class::<types::PrefixedBytes>()?;
+    m.add_class::<types::PascalString>()?;
+    m.add_class::<types::Tagged>()?;
+    m.add_class::<types::CEnum>()?;
+    m.add_class::<types::CFlags>()?;
+    m.add_class::<types::Pad>()?;
+    m.add_class::<types::Reserved>()?;
+    m.add_class::<types::Encoding>()?;
+    m.add_class::<types::Termination>()?;
     m.add_class::<types::CStruct>()?;
+    m.add_class::<types::CUnion>()?;
+    m.add_class::<types::Array>()?;
+    m.add_class::<types::Layout>()?;
+    m.add_class::<types::Records>()?;
+    m.add_class::<types::RecordIterator>()?;
+    m.add_class::<types::Field>()?;
+    m.add_class::<types::Overflow>()?;
+    m.add_class::<types::BitOrder>()?;
+    m.add_class::<types::UnknownValue>()?;
 
     m.add_class::<types::PyShaped>()?;
 
-    m.add("NativeEndian", types::NativeEndian())?;
-    m.add("BigEndian", types::BigEndian())?;
-    m.add("LittleEndian", types::LittleEndian())?;
+    m.add_class::<types::Endianness>()?;
+    m.add("NativeEndian", types::Endianness::Native)?;
+    m.add("LittleEndian", types::Endianness::Little)?;
+    m.add("BigEndian", types::Endianness::Big)?;
+
+    let errors = PyModule::new(py, "errors")?;
+    errors::register(py, errors)?;
+    m.add_submodule(errors)?;
+    // Allows `from _cerialize.errors import ...` since extension submodules aren't importable by themselves
+    py.import("sys")?
+        .getattr("modules")?
+        .set_item("_cerialize.errors", errors)?;
     Ok(())
 }
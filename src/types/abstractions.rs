@@ -1,52 +1,36 @@
+use super::{
+    declared_endianness, measure, numpy, Array, CStruct, Endianness, RecordIterator, Records, View,
+};
+use crate::errors::{BUFFER_SIZE_ERROR, SHAPE_ERROR};
+use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
-use pyo3::types::{PyCFunction, PyDict, PyLong, PyTuple, PyType};
+use pyo3::types::{PyBytes, PyDict, PyLong, PyString, PyTuple, PyType};
+use pyo3::{ffi, AsPyPointer};
 
 use itertools::Itertools;
 
 use once_cell::sync::Lazy;
 
 use std::collections::HashMap;
+use std::ffi::c_int;
 use std::hash::{Hash, Hasher};
 use std::sync::RwLock;
 
 static TYPE_CACHE: Lazy<RwLock<HashMap<TypeCacheKey, Py<PyType>>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
 
+/// Identifies a generated type by the class it is derived from and its name, which spells out its parameters (e.g. `u8[2,3]`),
+/// along with the parameters which are objects (e.g. the enum of a `cenum`) since their names don't tell them apart
 #[derive(Debug, Clone)]
-struct TypeCacheKey(Py<PyType>, Py<PyTuple>);
+struct TypeCacheKey(Py<PyType>, String, Vec<PyObject>);
 
 impl PartialEq for TypeCacheKey {
     fn eq(&self, other: &Self) -> bool {
-        let attributes = &["__qualname__"];
-
-        Python::with_gil(|py| {
-            let same_type = attributes.iter().all(|&attr| {
-                self.0
-                    .getattr(py, attr)
-                    .unwrap()
-                    .extract::<&str>(py)
-                    .unwrap()
-                    == other
-                        .0
-                        .getattr(py, attr)
-                        .unwrap()
-                        .extract::<&str>(py)
-                        .unwrap()
-            });
-
-            let same_tuple = self
-                .1
-                .as_ref(py)
-                .iter()
-                .zip_longest(other.1.as_ref(py).iter())
-                .all(|val| match val {
-                    itertools::EitherOrBoth::Both(left, right) => {
-                        left.extract::<usize>().unwrap() == right.extract::<usize>().unwrap()
-                    }
-                    _ => false,
-                });
-            same_type && same_tuple
-        })
+        // Classes are compared by identity so that two classes which happen to share a name don't share their array types
+        self.0.is(&other.0)
+            && self.1 == other.1
+            && self.2.len() == other.2.len()
+            && self.2.iter().zip(&other.2).all(|(a, b)| a.is(b))
     }
 }
 
@@ -54,183 +38,285 @@ impl Eq for TypeCacheKey {}
 
 impl Hash for TypeCacheKey {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        Python::with_gil(|py| {
-            self.0
-                .getattr(py, "__qualname__")
-                .unwrap()
-                .extract::<String>(py)
-                .unwrap()
-                .hash(state);
-            self.1
-                .as_ref(py)
-                .iter()
-                .map(|val| val.extract::<usize>())
-                .collect::<PyResult<Vec<usize>>>()
-                .unwrap()
-                .hash(state);
-        })
+        self.0.as_ptr().hash(state);
+        self.1.hash(state);
+        for parameter in &self.2 {
+            parameter.as_ptr().hash(state);
+        }
     }
 }
 
 #[pyclass(module = "_cerialize", name = "Shaped", subclass)]
-#[derive(Default)]
-pub struct PyShaped();
+pub struct PyShaped {
+    /// The packed representation of the value in the byte order given by `endianness`
+    pub(crate) buffer: View,
+    pub(crate) endianness: Endianness,
+}
 
 impl PyShaped {
-    fn derived_packed_size(cls: &PyType) -> PyResult<usize> {
-        let base_cls = cls.getattr("__origin__")?;
-        Ok(base_cls
-            .call_method1("__packed_size__", ())?
-            .extract::<usize>()?
-            * cls
-                .getattr("_SHAPE")?
-                .downcast::<PyTuple>()?
-                .into_iter()
-                .fold(1_usize, |prod, val| prod * val.extract::<usize>().unwrap()))
-    }
-
-    fn wrap_function<F>(py: Python<'_>, func: F) -> PyResult<Py<PyAny>>
-    where
-        F: Fn(&PyTuple, Option<&PyDict>) -> PyResult<Py<PyAny>> + Sync + Send + 'static,
-    {
-        // A simple function wrapper
-        // This is necessary so that you can take functions which are defined in rust and assign them to dynamically created classes with the correct arguments
-        let wrapper_fn: Py<PyAny> = PyModule::from_code(
-            py,
-            // TODO: This currently only supports functions that are meant to be class methods. It'd be nice to support other function types as well
-            "
-def wrapper(fn):
-    def _cerialize_function_wrapper(*args, **kwargs):
-        return fn(*args, **kwargs)
-    
-    return classmethod(_cerialize_function_wrapper)
-            ",
-            "abstractions.rs",
-            "",
-        )?
-        .getattr("wrapper")?
-        .into();
-
-        // Create a builtin python function from the function being called
-        let fn_arg = PyCFunction::new_closure(
-            py,
-            None,
-            None,
-            move |args: &PyTuple, kwargs: Option<&PyDict>| -> PyResult<Py<PyAny>> {
-                func(args, kwargs)
-            },
-        )?;
-
-        wrapper_fn.call1(py, (fn_arg,))
+    /// Creates a value which owns `buffer`
+    pub fn with_buffer(py: Python<'_>, buffer: Vec<u8>, endianness: Endianness) -> PyResult<Self> {
+        Ok(Self::with_view(View::new(py, buffer)?, endianness))
+    }
+
+    /// Creates a value which shares its memory with whoever else uses `buffer`
+    pub fn with_view(buffer: View, endianness: Endianness) -> Self {
+        Self { buffer, endianness }
+    }
+
+    /// Returns the packed bytes and the byte order of `value` if it is an instance of one of the shaped types
+    pub fn packed_bytes(value: &PyAny) -> Option<(Vec<u8>, Endianness)> {
+        let shaped = value.downcast::<PyCell<Self>>().ok()?.borrow();
+        Some((shaped.buffer.to_vec(value.py()), shaped.endianness))
+    }
+
+    /// Creates an instance of `cls` which reads from and writes to `view` rather than owning its memory
+    pub fn from_view(cls: &PyType, view: View, endianness: Endianness) -> PyResult<&PyAny> {
+        // Let the class allocate the instance as usual and then replace its contents
+        let instance = cls.call_method1("__new__", (cls,))?;
+        let mut shaped = instance.downcast::<PyCell<Self>>()?.borrow_mut();
+        shaped.buffer = view;
+        shaped.endianness = endianness;
+        Ok(instance)
+    }
+
+    /// Creates an instance of `cls` which uses `view` as its memory, keeping the byte order `cls` declares unless another one is given
+    pub fn decode(cls: &PyType, view: View, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let instance = cls.call_method1("__new__", (cls,))?;
+        let mut shaped = instance.downcast::<PyCell<Self>>()?.borrow_mut();
+        shaped.buffer = view;
+        if let Some(endianness) = endianness {
+            shaped.endianness = endianness;
+        }
+        drop(shaped);
+
+        // Decoding a struct checks its magic numbers and other constants against the buffer
+        if let Ok(cstruct) = instance.downcast::<PyCell<CStruct>>() {
+            CStruct::verify_constants(cstruct)?;
+        }
+        Ok(instance)
+    }
+
+    /// Creates the array type whose length is the value of the field named `count`, e.g. `Item["count"]`.
+    /// The field has to be an integer which is declared before the array in the same struct.
+    fn counted_type(cls: &PyType, count: &str) -> PyResult<Py<PyType>> {
+        let py = cls.py();
+        let class_name = format!("{}[{count}]", cls.name()?);
+        Self::derived_type(cls, class_name, |class_name| {
+            let kwds: HashMap<&str, &pyo3::PyAny> = HashMap::from_iter([
+                ("module", cls.getattr("__module__")?),
+                ("origin", cls.into()),
+            ]);
+            let new_type = PyModule::import(py, "types")?
+                .getattr("new_class")?
+                .call1((class_name, (py.get_type::<Array>(),), kwds))?
+                .downcast::<PyType>()?;
+            new_type.setattr("_COUNT", count)?;
+            Ok(new_type.into())
+        })
+    }
+
+    /// Returns the type named `name` which is derived from `cls`, calling `create` to create it the first time it is requested.
+    /// Caching the generated types makes sure that e.g. `u8[4] is u8[4]`.
+    pub fn derived_type(
+        cls: &PyType,
+        name: String,
+        create: impl FnOnce(&str) -> PyResult<Py<PyType>>,
+    ) -> PyResult<Py<PyType>> {
+        Self::derived_type_of(cls, name, &[], create)
+    }
+
+    /// Like `derived_type`, for types whose parameters include objects which are told apart by their identity rather than their name.
+    /// The cache keeps the objects alive so that a new object can't take the place of a cached one
+    pub fn derived_type_of(
+        cls: &PyType,
+        name: String,
+        parameters: &[&PyAny],
+        create: impl FnOnce(&str) -> PyResult<Py<PyType>>,
+    ) -> PyResult<Py<PyType>> {
+        let py = cls.py();
+        let parameters = parameters
+            .iter()
+            .map(|&parameter| parameter.into())
+            .collect();
+        let cache_key = TypeCacheKey(cls.into(), name, parameters);
+        {
+            let cache = TYPE_CACHE
+                .read()
+                .map_err(|_| PyRuntimeError::new_err("The type cache is poisoned"))?;
+            if let Some(cached) = cache.get(&cache_key) {
+                return Ok(cached.clone_ref(py));
+            }
+        }
+
+        let new_type = create(&cache_key.1)?;
+        let mut cache = TYPE_CACHE
+            .write()
+            .map_err(|_| PyRuntimeError::new_err("The type cache is poisoned"))?;
+        Ok(cache.entry(cache_key).or_insert(new_type).clone_ref(py))
     }
 }
 
 #[pymethods]
 impl PyShaped {
     #[new]
-    pub fn new() -> Self {
-        PyShaped::default()
+    pub fn new(py: Python<'_>) -> PyResult<Self> {
+        Self::with_buffer(py, Vec::new(), Endianness::Native)
+    }
+
+    /// Creates an instance of `cls` which is decoded from the first `__packed_size__()` bytes of any bytes-like object.
+    /// Writable buffers (e.g. `bytearray`, `memoryview`, `mmap`) aren't copied, so any modification of the instance lands in `buffer`.
+    #[classmethod]
+    #[pyo3(signature = (buffer, endianness = None))]
+    fn from_buffer<'py>(
+        cls: &'py PyType,
+        buffer: &PyAny,
+        endianness: Option<Endianness>,
+    ) -> PyResult<&'py PyAny> {
+        let view = View::of(buffer)?;
+        // The size of a dynamically sized value depends on the contents of the buffer
+        let measured_endianness = match endianness {
+            Some(endianness) => endianness,
+            None => declared_endianness(cls)?,
+        };
+        let size = view.read(cls.py(), |bytes| measure(cls, bytes, measured_endianness))?;
+        if view.len() < size {
+            return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                "{} requires a buffer of at least {size} bytes, got {}",
+                cls.name()?,
+                view.len()
+            )));
+        }
+
+        Self::decode(cls, view.slice(0, size), endianness)
+    }
+
+    /// Decodes `buffer` as back to back instances of `cls`, returning a lazy sequence of views into it.
+    /// Trailing bytes which don't make up a whole instance raise a `BufferSizeError` when `strict`, and are kept as `remainder` otherwise.
+    #[classmethod]
+    #[pyo3(signature = (buffer, endianness = None, *, strict = true))]
+    fn from_buffer_many(
+        cls: &PyType,
+        buffer: &PyAny,
+        endianness: Option<Endianness>,
+        strict: bool,
+    ) -> PyResult<Records> {
+        Records::new(cls, View::of(buffer)?, endianness, strict)
+    }
+
+    /// Iterates over the instances of `cls` packed back to back in `buffer`, see `from_buffer_many`
+    #[classmethod]
+    #[pyo3(signature = (buffer, endianness = None, *, strict = true))]
+    fn iter_unpack(
+        cls: &PyType,
+        buffer: &PyAny,
+        endianness: Option<Endianness>,
+        strict: bool,
+    ) -> PyResult<RecordIterator> {
+        let records = Records::new(cls, View::of(buffer)?, endianness, strict)?;
+        Ok(RecordIterator::new(Py::new(cls.py(), records)?))
+    }
+
+    /// Returns a NumPy array which shares its memory with this value, so writing to either modifies both
+    fn as_numpy(slf: &PyCell<Self>) -> PyResult<&PyAny> {
+        numpy::as_array(slf)
+    }
+
+    fn __bytes__<'py>(slf: PyRef<'py, Self>, py: Python<'py>) -> &'py PyBytes {
+        slf.buffer.read(py, |buffer| PyBytes::new(py, buffer))
+    }
+
+    /// Exposes the packed representation through the buffer protocol without copying it.
+    /// Buffers are read-only unless a writable one is requested (e.g. by `numpy.frombuffer` or `ctypes`).
+    /// Writing to a writable buffer modifies the value (and any value it is nested in), until a field which changes size reallocates the value.
+    unsafe fn __getbuffer__(
+        slf: &PyCell<Self>,
+        view: *mut ffi::Py_buffer,
+        flags: c_int,
+    ) -> PyResult<()> {
+        slf.borrow().buffer.export(slf.py(), view, flags)
+    }
+
+    unsafe fn __releasebuffer__(&self, _view: *mut ffi::Py_buffer) {
+        // `view.obj` is the storage which owns the exported memory, releasing the buffer releases the storage
     }
 
     #[pyo3(signature = (*args))]
     #[classmethod]
-    fn __class_getitem__(cls: &PyType, py: Python<'_>, args: &PyTuple) -> PyResult<Py<PyType>> {
+    pub(crate) fn __class_getitem__(
+        cls: &PyType,
+        py: Python<'_>,
+        args: &PyTuple,
+    ) -> PyResult<Py<PyType>> {
         // The shape is the 0th element of the tuple
         // If there is only one dimension then args[0] is just an int
         // If there are more than one dimensions then args[0] is a tuple of ints
         let shape_arg = args.get_item(0)?;
-
-        let shape = match (
-            shape_arg.is_instance_of::<PyTuple>(),
-            shape_arg.is_instance_of::<PyLong>(),
-        ) {
-            (true, false) => shape_arg
-                .downcast::<PyTuple>()?
-                .into_iter()
-                .map(|value| value.extract::<usize>())
-                .collect::<Vec<_>>(),
-            (false, true) => {
-                vec![shape_arg.extract::<usize>()]
-            }
-            _ => unreachable!("Uuh... What happened?"),
+        if let Ok(count) = shape_arg.downcast::<PyString>() {
+            return Self::counted_type(cls, count.to_str()?);
         }
-        .into_iter()
-        .collect::<Result<Vec<_>, _>>()?;
-
-        let cache_key = TypeCacheKey(
-            cls.into(),
-            Into::<Py<PyTuple>>::into(PyTuple::new(py, &shape)),
-        );
-
-        // Cache the generated type to avoid issues with overwriting attributes
-        if !TYPE_CACHE.read().unwrap().contains_key(&cache_key) {
-            let types = PyModule::import(py, "types").unwrap();
-            let class_name = format!("{}[{}]", cls.name().unwrap(), shape.iter().format(","));
-            let packed_size_fn = Self::wrap_function(
-                py,
-                |args: &PyTuple, _kwargs: Option<&PyDict>| -> PyResult<_> {
-                    let cls = args.get_item(0)?.downcast::<PyType>()?;
-                    Python::with_gil(|py| -> PyResult<Py<PyAny>> {
-                        Ok(Self::derived_packed_size(cls)?.to_object(py))
-                    })
-                },
-            )?;
+        let dimensions = match shape_arg.downcast::<PyTuple>() {
+            Ok(dimensions) => dimensions.iter().collect::<Vec<_>>(),
+            Err(_) => vec![shape_arg],
+        };
+
+        let name = cls.name()?;
+        let shape = dimensions
+            .into_iter()
+            .map(|dimension| match dimension.is_instance_of::<PyLong>() {
+                true => dimension.extract::<usize>().ok(),
+                false => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                SHAPE_ERROR.new_err(format!(
+                    "The dimensions of an array of {} must be non-negative integers, got {:?}",
+                    name, shape_arg
+                ))
+            })?;
+
+        // Every array type derives from `Array` which implements the indexing on top of the element type
+        let class_name = format!("{}[{}]", name, shape.iter().format(","));
+        Self::derived_type(cls, class_name, |class_name| {
             let kwds: HashMap<&str, &pyo3::PyAny> = HashMap::from_iter([
                 ("module", cls.getattr("__module__")?),
                 ("origin", cls.into()),
                 ("shape", PyTuple::new(py, &shape).into()),
-                ("packed_size_fn", packed_size_fn.as_ref(py)),
             ]);
-
-            TYPE_CACHE.write().unwrap().insert(
-                cache_key.clone(),
-                Into::<Py<PyType>>::into(
-                    types
-                        .getattr("new_class")
-                        .unwrap()
-                        .call1((class_name, (cls,), kwds))
-                        .unwrap()
-                        .downcast::<PyType>()
-                        .unwrap(),
-                ),
-            );
-        }
-
-        let cache = TYPE_CACHE.read().unwrap();
-        let new_type = cache.get(&cache_key).unwrap();
-        Ok(new_type.clone())
+            Ok(PyModule::import(py, "types")?
+                .getattr("new_class")?
+                .call1((class_name, (py.get_type::<Array>(),), kwds))?
+                .downcast::<PyType>()?
+                .into())
+        })
     }
 
     #[classmethod]
-    #[pyo3(signature = (*args, module, origin, packed_size_fn, shape = None, **kwargs))]
+    #[pyo3(signature = (*args, module = None, origin = None, shape = None, **kwargs))]
     fn __init_subclass__(
         cls: &PyType,
         args: &PyTuple,
-        module: &PyAny,
-        origin: &PyAny,
-        packed_size_fn: &PyAny,
+        module: Option<&PyAny>,
+        origin: Option<&PyAny>,
         shape: Option<&PyTuple>,
         kwargs: Option<&PyDict>,
     ) -> PyResult<()> {
         cls.py_super()?
             .call_method("__init_subclass__", args, kwargs)?;
 
-        packed_size_fn.setattr("__name__", "__packed_size__")?;
-        // This isn't working at the moment
-        // It may be due to the fact that this is a kinda bad way of doing it
-        // Either that or there is a bug in Pyo3
-        packed_size_fn.setattr(
-            "__qualname__",
-            format!(
-                "{}.__packed_size__",
-                cls.getattr("__qualname__")?.extract::<String>()?
-            ),
-        )?;
-
-        cls.setattr("__module__", module)?;
-        cls.setattr("__origin__", origin)?;
-        cls.setattr("__packed_size__", packed_size_fn)?;
-        cls.setattr("_SHAPE", shape)?;
+        // Regular subclasses (such as the classes generated by `cstruct`) don't provide any of the shape arguments
+        if let Some(module) = module {
+            cls.setattr("__module__", module)?;
+        }
+        if let Some(origin) = origin {
+            cls.setattr("__origin__", origin)?;
+        }
+        match shape {
+            Some(shape) => cls.setattr("_SHAPE", shape)?,
+            // Subclasses of an array type keep the shape of the array
+            None if !cls.hasattr("_SHAPE")? => cls.setattr("_SHAPE", shape)?,
+            None => {}
+        }
         Ok(())
     }
 }
@@ -0,0 +1,329 @@
+use super::{dtype_of, encode, Endianness, Overflow, PyShaped, View};
+use crate::errors::{BUFFER_SIZE_ERROR, SHAPE_ERROR};
+use pyo3::exceptions::PyIndexError;
+use pyo3::prelude::*;
+use pyo3::types::{PyList, PySlice, PyTuple, PyType};
+
+use itertools::Itertools;
+
+/// The base class of the types created by subscripting a shaped type, e.g. `f32[2]` or `u8[4, 4]`.
+/// The elements are stored back to back in one buffer in row-major order, and indexing returns views into it.
+#[pyclass(module = "_cerialize", name = "Array", subclass, weakref, extends=PyShaped)]
+pub struct Array();
+
+impl Array {
+    /// Returns the element type and the dimensions of an array class
+    pub fn dimensions(cls: &PyType) -> PyResult<(&PyType, Vec<usize>)> {
+        if let Some(count) = Self::count_of(cls)? {
+            return Err(SHAPE_ERROR.new_err(format!(
+                "The length of {} is the value of the field {count} of the struct it is declared in",
+                cls.name()?
+            )));
+        }
+        let shape = match cls.getattr("_SHAPE") {
+            Ok(shape) if !shape.is_none() => shape.downcast::<PyTuple>()?,
+            _ => {
+                return Err(SHAPE_ERROR.new_err(format!(
+                    "{} doesn't have a shape, create arrays by subscripting their element type",
+                    cls.name()?
+                )))
+            }
+        };
+
+        Ok((
+            cls.getattr("__origin__")?.downcast::<PyType>()?,
+            shape.extract::<Vec<usize>>()?,
+        ))
+    }
+
+    /// Returns the name of the field which holds the length of a counted array type, such as `Item["count"]`
+    pub fn count_of(cls: &PyType) -> PyResult<Option<String>> {
+        match cls.getattr("_COUNT") {
+            Ok(count) if !count.is_none() => Ok(Some(count.extract::<String>()?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the array type with `length` elements of the type a counted array type holds
+    pub fn with_length(counted: &PyType, length: usize) -> PyResult<&PyType> {
+        Self::type_of(
+            counted.getattr("__origin__")?.downcast::<PyType>()?,
+            &[length],
+        )
+    }
+
+    pub fn element_size(element_type: &PyType) -> PyResult<usize> {
+        element_type
+            .call_method0("__packed_size__")?
+            .extract::<usize>()
+    }
+
+    /// Returns the type of the values with the given dimensions, which is the element type itself if there are none
+    fn type_of<'py>(element_type: &'py PyType, shape: &[usize]) -> PyResult<&'py PyType> {
+        if shape.is_empty() {
+            return Ok(element_type);
+        }
+
+        // The declared types (e.g. `cerialize.u8`) are generic too, so subscripting them from Python would create a typing alias
+        let py = element_type.py();
+        let shape = PyTuple::new(py, shape);
+        Ok(PyShaped::__class_getitem__(element_type, py, PyTuple::new(py, [shape]))?.into_ref(py))
+    }
+
+    /// Converts an index of a dimension of `length` elements to a position, counting from the back for negative indices
+    fn position(index: &PyAny, length: usize) -> PyResult<usize> {
+        let index = index.extract::<isize>()?;
+        let position = match index < 0 {
+            true => index + length as isize,
+            false => index,
+        };
+
+        match 0 <= position && position < length as isize {
+            true => Ok(position as usize),
+            false => Err(PyIndexError::new_err(format!(
+                "Index {index} is out of range for a dimension of {length} elements"
+            ))),
+        }
+    }
+
+    fn positions(slice: &PySlice, length: usize) -> PyResult<(Vec<usize>, isize)> {
+        let indices = slice.indices(length as std::os::raw::c_long)?;
+        let positions = (0..indices.slicelength)
+            .map(|step| (indices.start + step * indices.step) as usize)
+            .collect();
+        Ok((positions, indices.step))
+    }
+
+    /// Splits an index into the index of each dimension
+    fn components(index: &PyAny) -> Vec<&PyAny> {
+        match index.downcast::<PyTuple>() {
+            Ok(components) => components.iter().collect(),
+            Err(_) => vec![index],
+        }
+    }
+
+    /// Returns the value at `index` within `view`, which holds values of the given element type and dimensions.
+    /// A slice with a step of one is returned as an array view, anything else which selects several values as a list of views.
+    fn select<'py>(
+        py: Python<'py>,
+        element_type: &'py PyType,
+        view: &View,
+        shape: &[usize],
+        index: &[&PyAny],
+        endianness: Endianness,
+    ) -> PyResult<&'py PyAny> {
+        let Some((&first, rest)) = index.split_first() else {
+            return PyShaped::from_view(
+                Self::type_of(element_type, shape)?,
+                view.clone(),
+                endianness,
+            );
+        };
+        let Some((&length, inner)) = shape.split_first() else {
+            return Err(PyIndexError::new_err("Too many indices for the array"));
+        };
+        let stride = Self::element_size(element_type)? * inner.iter().product::<usize>();
+
+        if let Ok(slice) = first.downcast::<PySlice>() {
+            let (positions, step) = Self::positions(slice, length)?;
+            if rest.is_empty() && step == 1 {
+                let start = positions.first().copied().unwrap_or(0);
+                let shape = [&[positions.len()], inner].concat();
+                return PyShaped::from_view(
+                    Self::type_of(element_type, &shape)?,
+                    view.slice(start * stride, positions.len() * stride),
+                    endianness,
+                );
+            }
+
+            let values = positions
+                .into_iter()
+                .map(|position| {
+                    let view = view.slice(position * stride, stride);
+                    Self::select(py, element_type, &view, inner, rest, endianness)
+                })
+                .collect::<PyResult<Vec<_>>>()?;
+            return Ok(PyList::new(py, values));
+        }
+
+        let position = Self::position(first, length)?;
+        let view = view.slice(position * stride, stride);
+        Self::select(py, element_type, &view, inner, rest, endianness)
+    }
+
+    /// Formats the values as a (nested) list, the same form the array can be created from
+    fn values_repr(slf: &PyCell<Self>) -> PyResult<String> {
+        let values = Self::__iter__(slf)?
+            .iter()?
+            .map(|value| {
+                let value = value?;
+                match value.downcast::<PyCell<Self>>() {
+                    Ok(row) => Self::values_repr(row),
+                    Err(_) => Ok(value.repr()?.to_string()),
+                }
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(format!("[{}]", values.iter().format(", ")))
+    }
+
+    /// Encodes `value` into the values selected by `index`, the counterpart of `select`
+    fn assign(
+        element_type: &PyType,
+        view: &View,
+        shape: &[usize],
+        index: &[&PyAny],
+        value: &PyAny,
+        endianness: Endianness,
+    ) -> PyResult<()> {
+        let py = element_type.py();
+        let Some((&first, rest)) = index.split_first() else {
+            let encoded = encode(Self::type_of(element_type, shape)?, value, endianness, None)?;
+            if encoded.len() != view.len() {
+                return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                    "Expected {} bytes for a value of {}, got {} bytes",
+                    view.len(),
+                    Self::type_of(element_type, shape)?.name()?,
+                    encoded.len()
+                )));
+            }
+            view.write(py, 0, &encoded);
+            return Ok(());
+        };
+        let Some((&length, inner)) = shape.split_first() else {
+            return Err(PyIndexError::new_err("Too many indices for the array"));
+        };
+        let stride = Self::element_size(element_type)? * inner.iter().product::<usize>();
+
+        if let Ok(slice) = first.downcast::<PySlice>() {
+            let (positions, _) = Self::positions(slice, length)?;
+            if value.len()? != positions.len() {
+                return Err(SHAPE_ERROR.new_err(format!(
+                    "Expected {} values for the slice, got {}",
+                    positions.len(),
+                    value.len()?
+                )));
+            }
+
+            for (position, value) in positions.into_iter().zip(value.iter()?) {
+                let view = view.slice(position * stride, stride);
+                Self::assign(element_type, &view, inner, rest, value?, endianness)?;
+            }
+            return Ok(());
+        }
+
+        let position = Self::position(first, length)?;
+        let view = view.slice(position * stride, stride);
+        Self::assign(element_type, &view, inner, rest, value, endianness)
+    }
+}
+
+#[pymethods]
+impl Array {
+    /// Creates a zeroed array, or one holding `value` which is a (nested) sequence with one value for each element or the packed bytes of the array
+    #[new]
+    #[classmethod]
+    #[pyo3(signature = (value = None, endianness = None, overflow = None))]
+    fn new(
+        cls: &PyType,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+        overflow: Option<Overflow>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or_default();
+        let size = Self::__packed_size__(cls)?;
+        let buffer = match value {
+            Some(value) => encode(cls, value, endianness, overflow)?,
+            None => vec![0; size],
+        };
+        if buffer.len() != size {
+            return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                "{} requires a buffer of exactly {size} bytes, got {}",
+                cls.name()?,
+                buffer.len()
+            )));
+        }
+
+        Ok((Self(), PyShaped::with_buffer(cls.py(), buffer, endianness)?))
+    }
+
+    #[classmethod]
+    fn __packed_size__(cls: &PyType) -> PyResult<usize> {
+        let (element_type, shape) = Self::dimensions(cls)?;
+        Ok(Self::element_size(element_type)? * shape.iter().product::<usize>())
+    }
+
+    #[classmethod]
+    fn __alignment__(cls: &PyType) -> PyResult<usize> {
+        // Counted arrays don't have dimensions but are aligned all the same
+        cls.getattr("__origin__")?
+            .call_method0("__alignment__")?
+            .extract::<usize>()
+    }
+
+    /// Returns the NumPy sub-array dtype of the array, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let py = cls.py();
+        let (element_type, shape) = Self::dimensions(cls)?;
+        let element = dtype_of(element_type, endianness.unwrap_or_default())?;
+        py.import("numpy")?
+            .getattr("dtype")?
+            .call1(((element, PyTuple::new(py, shape)),))
+    }
+
+    /// The number of elements along each dimension
+    #[getter]
+    fn shape(slf: &PyCell<Self>) -> PyResult<&PyTuple> {
+        let (_, shape) = Self::dimensions(slf.get_type())?;
+        Ok(PyTuple::new(slf.py(), shape))
+    }
+
+    fn __len__(slf: &PyCell<Self>) -> PyResult<usize> {
+        let (_, shape) = Self::dimensions(slf.get_type())?;
+        Ok(shape.first().copied().unwrap_or(0))
+    }
+
+    fn __getitem__<'py>(slf: &'py PyCell<Self>, index: &PyAny) -> PyResult<&'py PyAny> {
+        let (element_type, shape) = Self::dimensions(slf.get_type())?;
+        let shaped = slf.borrow();
+        let shaped = shaped.as_ref();
+        Self::select(
+            slf.py(),
+            element_type,
+            &shaped.buffer,
+            &shape,
+            &Self::components(index),
+            shaped.endianness,
+        )
+    }
+
+    fn __setitem__(slf: &PyCell<Self>, index: &PyAny, value: &PyAny) -> PyResult<()> {
+        let (element_type, shape) = Self::dimensions(slf.get_type())?;
+        let shaped = slf.borrow();
+        let shaped = shaped.as_ref();
+        Self::assign(
+            element_type,
+            &shaped.buffer,
+            &shape,
+            &Self::components(index),
+            value,
+            shaped.endianness,
+        )
+    }
+
+    fn __iter__(slf: &PyCell<Self>) -> PyResult<&PyAny> {
+        let values = (0..Self::__len__(slf)?)
+            .map(|position| Self::__getitem__(slf, position.into_py(slf.py()).into_ref(slf.py())))
+            .collect::<PyResult<Vec<_>>>()?;
+        PyList::new(slf.py(), values).call_method0("__iter__")
+    }
+
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
+        Ok(format!(
+            "{}({})",
+            slf.get_type().name()?,
+            Self::values_repr(slf)?
+        ))
+    }
+}
@@ -0,0 +1,187 @@
+use super::{
+    BitOrder, Endianness, Int16, Int32, Int64, Int8, Overflow, Uint16, Uint32, Uint64, Uint8,
+};
+use crate::errors::{LAYOUT_ERROR, VALUE_RANGE_ERROR};
+use pyo3::prelude::*;
+use pyo3::types::PyType;
+
+/// Returns the number of bits of the integer type `type_` and whether it is signed, or `None` if bitfields can't be stored in it
+fn storage_unit(type_: &PyType) -> PyResult<Option<(u32, bool)>> {
+    let py = type_.py();
+    for (unit, width, signed) in [
+        (py.get_type::<Int8>(), 8, true),
+        (py.get_type::<Int16>(), 16, true),
+        (py.get_type::<Int32>(), 32, true),
+        (py.get_type::<Int64>(), 64, true),
+        (py.get_type::<Uint8>(), 8, false),
+        (py.get_type::<Uint16>(), 16, false),
+        (py.get_type::<Uint32>(), 32, false),
+        (py.get_type::<Uint64>(), 64, false),
+    ] {
+        if type_.is_subclass(unit)? {
+            return Ok(Some((width, signed)));
+        }
+    }
+    Ok(None)
+}
+
+/// Whether a storage unit in the given byte order has its least significant byte first
+fn is_little(endianness: Endianness) -> bool {
+    match endianness {
+        Endianness::Native => cfg!(target_endian = "little"),
+        Endianness::Little => true,
+        Endianness::Big => false,
+    }
+}
+
+/// The bits a bitfield occupies within the integer it is stored in, e.g. `uint32_t flags : 3;`
+#[derive(Clone, Copy, Debug)]
+pub struct Bits {
+    /// The position of the least significant bit of the bitfield within the value of its storage unit
+    pub shift: u32,
+    pub width: u32,
+    /// Whether values are sign extended, which follows the signedness of the storage unit's type
+    pub signed: bool,
+    /// Whether the bitfield is stored in the same unit as the field before it
+    pub shared: bool,
+}
+
+impl Bits {
+    fn mask(&self) -> u128 {
+        u128::MAX >> (128 - self.width)
+    }
+
+    /// The smallest and largest values the bitfield can hold
+    fn range(&self) -> (i128, i128) {
+        match self.signed {
+            true => (-(1 << (self.width - 1)), (1 << (self.width - 1)) - 1),
+            false => (0, self.mask() as i128),
+        }
+    }
+
+    fn unit(buffer: &[u8], endianness: Endianness) -> u128 {
+        let mut bytes = [0; 16];
+        bytes[..buffer.len()].copy_from_slice(buffer);
+        if !is_little(endianness) {
+            bytes[..buffer.len()].reverse();
+        }
+        u128::from_le_bytes(bytes)
+    }
+
+    /// Reads the bitfield from the packed storage unit in `buffer`
+    pub fn read(&self, buffer: &[u8], endianness: Endianness) -> i128 {
+        let bits = (Self::unit(buffer, endianness) >> self.shift) & self.mask();
+        match self.signed {
+            // Moving the sign bit of the bitfield to the top makes the arithmetic shift back extend it
+            true => ((bits << (128 - self.width)) as i128) >> (128 - self.width),
+            false => bits as i128,
+        }
+    }
+
+    /// Returns the storage unit in `buffer` with the bitfield named `name` replaced by `value`, leaving the other bits as they are.
+    /// Values which don't fit into the bitfield are handled according to `overflow`.
+    pub fn write(
+        &self,
+        name: &str,
+        buffer: &[u8],
+        value: &PyAny,
+        endianness: Endianness,
+        overflow: Overflow,
+    ) -> PyResult<Vec<u8>> {
+        let value = value.call_method0("__index__")?;
+        let (min, max) = self.range();
+        let bits = match value.extract::<i128>() {
+            Ok(literal) if (min..=max).contains(&literal) => literal as u128,
+            _ => match overflow {
+                Overflow::Error => {
+                    return Err(VALUE_RANGE_ERROR.new_err(format!(
+                        "{value} is out of range for the {}-bit field {name} ({min}..={max})",
+                        self.width
+                    )))
+                }
+                Overflow::Wrap => value
+                    .call_method1("__and__", (self.mask(),))?
+                    .extract::<u128>()?,
+                Overflow::Saturate => match value.lt(0)? {
+                    true => min as u128,
+                    false => max as u128,
+                },
+            },
+        };
+
+        let unit = (Self::unit(buffer, endianness) & !(self.mask() << self.shift))
+            | ((bits & self.mask()) << self.shift);
+        let mut packed = unit.to_le_bytes()[..buffer.len()].to_vec();
+        if !is_little(endianness) {
+            packed.reverse();
+        }
+        Ok(packed)
+    }
+}
+
+/// Packs consecutive bitfields into storage units while a layout is compiled.
+/// A bitfield shares the unit of the bitfield before it if both are declared with the same type and byte order and there are enough bits left,
+/// otherwise it starts a new unit.
+pub struct BitAllocator {
+    order: BitOrder,
+    /// The type and byte order of the current storage unit and the number of its bits which are taken
+    unit: Option<(Py<PyType>, Option<Endianness>, u32)>,
+}
+
+impl BitAllocator {
+    pub fn new(order: BitOrder) -> Self {
+        Self { order, unit: None }
+    }
+
+    /// Allocates `width` bits of a storage unit of type `type_` for the bitfield `name` of `cls`
+    pub fn allocate(
+        &mut self,
+        cls: &PyType,
+        name: &str,
+        type_: &PyType,
+        width: u32,
+        endianness: Option<Endianness>,
+    ) -> PyResult<Bits> {
+        let Some((unit_width, signed)) = storage_unit(type_)? else {
+            return Err(LAYOUT_ERROR.new_err(format!(
+                "The bitfield {name} of {} must be stored in an integer of at most 64 bits, got {}",
+                cls.name()?,
+                type_.name()?
+            )));
+        };
+        if width == 0 || width > unit_width {
+            return Err(LAYOUT_ERROR.new_err(format!(
+                "The bitfield {name} of {} must be between 1 and {unit_width} bits wide, got {width}",
+                cls.name()?
+            )));
+        }
+
+        let (position, shared) = match &self.unit {
+            Some((unit, unit_endianness, used))
+                if unit.as_ref(type_.py()).is(type_)
+                    && *unit_endianness == endianness
+                    && used + width <= unit_width =>
+            {
+                (*used, true)
+            }
+            _ => (0, false),
+        };
+        self.unit = Some((type_.into(), endianness, position + width));
+
+        let shift = match self.order {
+            BitOrder::LsbFirst => position,
+            BitOrder::MsbFirst => unit_width - position - width,
+        };
+        Ok(Bits {
+            shift,
+            width,
+            signed,
+            shared,
+        })
+    }
+
+    /// Ends the current storage unit, since the field after it isn't a bitfield
+    pub fn close(&mut self) {
+        self.unit = None;
+    }
+}
@@ -0,0 +1,270 @@
+use super::{Endianness, Overflow, PyShaped, UnknownValue};
+use crate::errors::{LAYOUT_ERROR, VALUE_RANGE_ERROR};
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyBytes, PyDict, PyString, PyTuple, PyType};
+
+/// An integer whose values have symbolic names, e.g. `cenum[u8, Status]` for the `enum.IntEnum` `Status`.
+/// The names can also be given as a dict (`cenum[u8, {"idle": 0, "busy": 1}]`), which creates the `IntEnum` from them.
+/// Fields of this type are read as members of the enum and can be assigned members, their names or plain integers.
+/// Values without a name raise a `ValueRangeError`, unless `UnknownValue.keep` follows the enum: then they are read as plain `int`s.
+#[pyclass(module = "_cerialize", name = "cenum", subclass, weakref, extends=PyShaped)]
+pub struct CEnum();
+
+impl CEnum {
+    /// Returns the integer type, the enum and the policy for unknown values of an enum class
+    fn options(cls: &PyType) -> PyResult<(&PyType, &PyType, UnknownValue)> {
+        match cls.getattr("_BASE") {
+            Ok(base) if !base.is_none() => Ok((
+                base.downcast::<PyType>()?,
+                cls.getattr("_ENUM")?.downcast::<PyType>()?,
+                cls.getattr("_UNKNOWN")?.extract::<UnknownValue>()?,
+            )),
+            _ => Err(LAYOUT_ERROR.new_err(format!(
+                "{} doesn't have an integer type, create enums by subscripting it, e.g. cenum[u8, Status]",
+                cls.name()?
+            ))),
+        }
+    }
+
+    /// Converts a member, the name of a member or an integer into the integer it stands for
+    fn resolve<'py>(cls: &'py PyType, value: &'py PyAny) -> PyResult<&'py PyAny> {
+        let (_, members, unknown) = Self::options(cls)?;
+        if let Ok(name) = value.downcast::<PyString>() {
+            return match members.getattr("__members__")?.get_item(name) {
+                Ok(member) => member.call_method0("__index__"),
+                Err(_) => Err(VALUE_RANGE_ERROR.new_err(format!(
+                    "{} isn't the name of a member of {}",
+                    name.repr()?,
+                    members.name()?
+                ))),
+            };
+        }
+        if let Ok(value) = value.downcast::<PyCell<Self>>() {
+            return Self::integer(value);
+        }
+        if !value.hasattr("__index__")? {
+            return Err(PyTypeError::new_err(format!(
+                "{} can't be created from a value of type {}",
+                cls.name()?,
+                value.get_type().name()?
+            )));
+        }
+
+        let integer = value.call_method0("__index__")?;
+        if unknown == UnknownValue::Error && members.call1((integer,)).is_err() {
+            return Err(VALUE_RANGE_ERROR.new_err(format!(
+                "{integer} isn't the value of a member of {}",
+                members.name()?
+            )));
+        }
+        Ok(integer)
+    }
+
+    /// Returns the member of the enum with the value `integer`, or `integer` itself if it has no name and unknown values are kept
+    fn member<'py>(cls: &'py PyType, integer: &'py PyAny) -> PyResult<&'py PyAny> {
+        let (_, members, unknown) = Self::options(cls)?;
+        match (members.call1((integer,)), unknown) {
+            (Ok(member), _) => Ok(member),
+            (Err(_), UnknownValue::Keep) => Ok(integer),
+            (Err(_), UnknownValue::Error) => Err(VALUE_RANGE_ERROR.new_err(format!(
+                "{integer} isn't the value of a member of {}",
+                members.name()?
+            ))),
+        }
+    }
+
+    /// Decodes the member in `buffer`, which is how fields of this type are read
+    pub fn value_of<'py>(
+        cls: &PyType,
+        py: Python<'py>,
+        buffer: &[u8],
+        endianness: Endianness,
+    ) -> PyResult<&'py PyAny> {
+        let (base, _, _) = Self::options(cls)?;
+        let integer = base
+            .call1((PyBytes::new(cls.py(), buffer), endianness))?
+            .call_method0("__index__")?;
+        let member: PyObject = Self::member(cls, integer)?.into();
+        Ok(member.into_ref(py))
+    }
+
+    fn integer(slf: &PyCell<Self>) -> PyResult<&PyAny> {
+        let (base, _, _) = Self::options(slf.get_type())?;
+        let shaped = slf.borrow();
+        let shaped = shaped.as_ref();
+        let packed = shaped.buffer.to_vec(slf.py());
+        base.call1((PyBytes::new(slf.py(), &packed), shaped.endianness))?
+            .call_method0("__index__")
+    }
+}
+
+#[pymethods]
+impl CEnum {
+    /// Creates a zeroed value, or one holding `value` which is a member, the name of a member, an integer or the packed bytes of the value
+    #[new]
+    #[classmethod]
+    #[pyo3(signature = (value = None, endianness = None, overflow = None))]
+    fn new(
+        cls: &PyType,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+        overflow: Option<Overflow>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let py = cls.py();
+        let (base, _, _) = Self::options(cls)?;
+        let endianness = endianness.unwrap_or_default();
+
+        // The integer type takes care of the byte order, the size of packed bytes and values which don't fit into it
+        let kwargs = overflow.map(|overflow| [("overflow", overflow.into_py(py))].into_py_dict(py));
+        let instance = match value {
+            None => base.call((0, endianness), kwargs)?,
+            Some(value) if value.is_instance_of::<PyString>() || value.hasattr("__index__")? => {
+                base.call((Self::resolve(cls, value)?, endianness), kwargs)?
+            }
+            Some(value) => base.call((value, endianness), kwargs)?,
+        };
+        let buffer = instance
+            .downcast::<PyCell<PyShaped>>()?
+            .borrow()
+            .buffer
+            .to_vec(py);
+
+        Ok((Self(), PyShaped::with_buffer(py, buffer, endianness)?))
+    }
+
+    /// `cenum[integer_type, enum, unknown]` creates an enum type, subscripting an enum type creates an array of enums
+    #[pyo3(signature = (*args))]
+    #[classmethod]
+    fn __class_getitem__(cls: &PyType, args: &PyTuple) -> PyResult<Py<PyType>> {
+        let py = cls.py();
+        if Self::options(cls).is_ok() {
+            return PyShaped::__class_getitem__(cls, py, args);
+        }
+
+        let name = cls.name()?;
+        let arg = args.get_item(0)?;
+        let options = match arg.downcast::<PyTuple>() {
+            Ok(options) => options.iter().collect::<Vec<_>>(),
+            Err(_) => vec![arg],
+        };
+        let (base, members, unknown) = match options.as_slice() {
+            [base, members] => (*base, *members, UnknownValue::default()),
+            [base, members, unknown] => (*base, *members, unknown.extract::<UnknownValue>()?),
+            _ => {
+                return Err(LAYOUT_ERROR.new_err(format!(
+                    "{name} must be subscripted with an integer type and an enum, optionally followed by an UnknownValue"
+                )))
+            }
+        };
+
+        let base = base
+            .downcast::<PyType>()
+            .ok()
+            .filter(|base| {
+                base.is_subclass_of::<PyShaped>().unwrap_or(false)
+                    && base.hasattr("__index__").unwrap_or(false)
+            })
+            .ok_or_else(|| {
+                LAYOUT_ERROR.new_err(format!(
+                    "The values of {name} must be stored in an integer type, got {base}"
+                ))
+            })?;
+
+        // Names given as a dict become an `IntEnum` so that fields are read as members either way.
+        // The same names give the same type, so the enum is only created once
+        let int_enum = py.import("enum")?.getattr("IntEnum")?;
+        let (names, objects) = match members.downcast::<PyDict>() {
+            Ok(names) => (names.repr()?.to_string(), vec![base.as_ref()]),
+            Err(_) => match members.downcast::<PyType>() {
+                Ok(enum_type) if enum_type.is_subclass(int_enum)? => {
+                    (enum_type.name()?.to_string(), vec![base.as_ref(), members])
+                }
+                _ => {
+                    return Err(LAYOUT_ERROR.new_err(format!(
+                        "The names of {name} must be an enum.IntEnum or a dict from names to values, got {members}"
+                    )))
+                }
+            },
+        };
+
+        let mut parameters = vec![base.name()?.to_string(), names];
+        if unknown != UnknownValue::default() {
+            parameters.push(unknown.name().to_string());
+        }
+        let class_name = format!("{name}[{}]", parameters.join(", "));
+        PyShaped::derived_type_of(cls, class_name, &objects, |class_name| {
+            let members = match members.downcast::<PyDict>() {
+                Ok(names) => int_enum.call1((name, names))?,
+                Err(_) => members,
+            };
+            let new_type = py
+                .import("types")?
+                .getattr("new_class")?
+                .call1((class_name, (cls,)))?
+                .downcast::<PyType>()?;
+            new_type.setattr("__module__", cls.getattr("__module__")?)?;
+            new_type.setattr("_BASE", base)?;
+            new_type.setattr("_ENUM", members)?;
+            new_type.setattr("_UNKNOWN", unknown.into_py(py))?;
+            Ok(new_type.into())
+        })
+    }
+
+    /// The member of the enum, or a plain `int` if the value has no name and unknown values are kept
+    #[getter]
+    fn value(slf: &PyCell<Self>) -> PyResult<&PyAny> {
+        Self::member(slf.get_type(), Self::integer(slf)?)
+    }
+
+    fn __index__(slf: &PyCell<Self>) -> PyResult<&PyAny> {
+        Self::integer(slf)
+    }
+
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
+        let value = Self::value(slf)?;
+        // Members are shown the way they are written, e.g. `Status.busy`
+        let value = match value.hasattr("name")? {
+            true => format!("{}.{}", value.get_type().name()?, value.getattr("name")?),
+            false => value.repr()?.to_string(),
+        };
+        Ok(format!("{}({value})", slf.get_type().name()?))
+    }
+
+    /// Values compare by their member, both with each other and with members or integers
+    fn __richcmp__(slf: &PyCell<Self>, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        let other = match other.downcast::<PyCell<Self>>() {
+            Ok(other) => Self::value(other)?,
+            Err(_) => other,
+        };
+        Ok(Self::value(slf)?.rich_compare(other, op)?.into())
+    }
+
+    fn __hash__(slf: &PyCell<Self>) -> PyResult<isize> {
+        Self::value(slf)?.hash()
+    }
+
+    #[classmethod]
+    fn __packed_size__(cls: &PyType) -> PyResult<usize> {
+        Self::options(cls)?
+            .0
+            .call_method0("__packed_size__")?
+            .extract::<usize>()
+    }
+
+    #[classmethod]
+    fn __alignment__(cls: &PyType) -> PyResult<usize> {
+        Self::options(cls)?
+            .0
+            .call_method0("__alignment__")?
+            .extract::<usize>()
+    }
+
+    /// The NumPy dtype of the integer type, since NumPy has no enums
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        Self::options(cls)?.0.call_method1("dtype", (endianness,))
+    }
+}
@@ -0,0 +1,367 @@
+use super::{bytes_of, Endianness, Overflow, PyShaped, Uint16, Uint32, Uint64, Uint8};
+use crate::errors::{LAYOUT_ERROR, VALUE_RANGE_ERROR};
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyBytes, PyDict, PyString, PyTuple, PyType};
+
+use itertools::Itertools;
+
+/// A set of bit flags stored in an unsigned integer, e.g. `cflags[u8, Permissions]` for the `enum.IntFlag` `Permissions`.
+/// The flags can also be given as a dict (`cflags[u8, {"read": 1, "write": 2}]`), which creates the `IntFlag` from them.
+/// Values behave like a set of the flags which are set and can be assigned members, names, plain integers or iterables of them.
+/// Bits which don't belong to any flag are kept as they are and shown after the names of the flags, e.g. `read|write|0x10`.
+#[pyclass(module = "_cerialize", name = "cflags", subclass, weakref, extends=PyShaped)]
+pub struct CFlags();
+
+impl CFlags {
+    /// Returns the integer type and the flags of a flag set class
+    fn options(cls: &PyType) -> PyResult<(&PyType, &PyType)> {
+        match cls.getattr("_BASE") {
+            Ok(base) if !base.is_none() => Ok((
+                base.downcast::<PyType>()?,
+                cls.getattr("_FLAGS")?.downcast::<PyType>()?,
+            )),
+            _ => Err(LAYOUT_ERROR.new_err(format!(
+                "{} doesn't have an integer type, create flag sets by subscripting it, e.g. cflags[u8, Permissions]",
+                cls.name()?
+            ))),
+        }
+    }
+
+    /// Converts a flag, the name of a flag, an integer or an iterable of them into the bits they stand for
+    fn resolve<'py>(cls: &'py PyType, value: &'py PyAny) -> PyResult<&'py PyAny> {
+        let (_, flags) = Self::options(cls)?;
+        if let Ok(name) = value.downcast::<PyString>() {
+            return match flags.getattr("__members__")?.get_item(name) {
+                Ok(flag) => flag.call_method0("__index__"),
+                Err(_) => Err(VALUE_RANGE_ERROR.new_err(format!(
+                    "{} isn't the name of a flag of {}",
+                    name.repr()?,
+                    flags.name()?
+                ))),
+            };
+        }
+        if let Ok(value) = value.downcast::<PyCell<Self>>() {
+            return Self::integer(value);
+        }
+        if value.hasattr("__index__")? {
+            return value.call_method0("__index__");
+        }
+
+        let Ok(items) = value.iter() else {
+            return Err(PyTypeError::new_err(format!(
+                "{} can't be created from a value of type {}",
+                cls.name()?,
+                value.get_type().name()?
+            )));
+        };
+        let mut integer = 0u64.into_py(cls.py()).into_ref(cls.py());
+        for item in items {
+            integer = integer.call_method1("__or__", (Self::resolve(cls, item?)?,))?;
+        }
+        Ok(integer)
+    }
+
+    fn integer(slf: &PyCell<Self>) -> PyResult<&PyAny> {
+        let (base, _) = Self::options(slf.get_type())?;
+        let shaped = slf.borrow();
+        let shaped = shaped.as_ref();
+        let packed = shaped.buffer.to_vec(slf.py());
+        base.call1((PyBytes::new(slf.py(), &packed), shaped.endianness))?
+            .call_method0("__index__")
+    }
+
+    fn bits(slf: &PyCell<Self>) -> PyResult<u64> {
+        Self::integer(slf)?.extract::<u64>()
+    }
+
+    /// Returns the flags which are set in `bits`, in the order they are declared, and the bits which don't belong to any of them
+    fn split(cls: &PyType, bits: u64) -> PyResult<(Vec<&PyAny>, u64)> {
+        let (_, flags) = Self::options(cls)?;
+        let mut set = Vec::new();
+        let mut residual = bits;
+        // Iterating an `IntFlag` only yields its canonical (single bit) flags, which is what a value is made of
+        for flag in flags.iter()? {
+            let flag = flag?;
+            let value = flag.call_method0("__index__")?.extract::<u64>()?;
+            if value != 0 && bits & value == value {
+                set.push(flag);
+                residual &= !value;
+            }
+        }
+        Ok((set, residual))
+    }
+
+    /// Packs `integer` with the integer type and byte order of `slf`
+    fn pack(slf: &PyCell<Self>, integer: &PyAny) -> PyResult<Vec<u8>> {
+        let py = slf.py();
+        let (base, _) = Self::options(slf.get_type())?;
+        let endianness = slf.borrow().as_ref().endianness;
+        let instance = base.call1((integer, endianness))?;
+        let buffer = instance
+            .downcast::<PyCell<PyShaped>>()?
+            .borrow()
+            .buffer
+            .to_vec(py);
+        Ok(buffer)
+    }
+
+    /// Writes `integer` into the memory of `slf`, which is the struct's memory if `slf` is a field
+    fn store(slf: &PyCell<Self>, integer: &PyAny) -> PyResult<()> {
+        let packed = Self::pack(slf, integer)?;
+        slf.borrow().as_ref().buffer.write(slf.py(), 0, &packed);
+        Ok(())
+    }
+
+    /// Creates a new flag set of the same type and byte order as `slf` holding `integer`
+    fn derive(slf: &PyCell<Self>, integer: u64) -> PyResult<&PyAny> {
+        let endianness = slf.borrow().as_ref().endianness;
+        slf.get_type().call1((integer, endianness))
+    }
+}
+
+#[pymethods]
+impl CFlags {
+    /// Creates an empty flag set, or one holding `value` which is a flag, the name of a flag, an integer, an iterable of them or the packed bytes of the value
+    #[new]
+    #[classmethod]
+    #[pyo3(signature = (value = None, endianness = None, overflow = None))]
+    fn new(
+        cls: &PyType,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+        overflow: Option<Overflow>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let py = cls.py();
+        let (base, _) = Self::options(cls)?;
+        let endianness = endianness.unwrap_or_default();
+
+        // The integer type takes care of the byte order, the size of packed bytes and values which don't fit into it
+        let kwargs = overflow.map(|overflow| [("overflow", overflow.into_py(py))].into_py_dict(py));
+        let instance = match value {
+            None => base.call((0, endianness), kwargs)?,
+            Some(value)
+                if PyShaped::packed_bytes(value).is_none() && bytes_of(value)?.is_some() =>
+            {
+                base.call((value, endianness), kwargs)?
+            }
+            Some(value) => base.call((Self::resolve(cls, value)?, endianness), kwargs)?,
+        };
+        let buffer = instance
+            .downcast::<PyCell<PyShaped>>()?
+            .borrow()
+            .buffer
+            .to_vec(py);
+
+        Ok((Self(), PyShaped::with_buffer(py, buffer, endianness)?))
+    }
+
+    /// `cflags[integer_type, flags]` creates a flag set type, subscripting a flag set type creates an array of them
+    #[pyo3(signature = (*args))]
+    #[classmethod]
+    fn __class_getitem__(cls: &PyType, args: &PyTuple) -> PyResult<Py<PyType>> {
+        let py = cls.py();
+        if Self::options(cls).is_ok() {
+            return PyShaped::__class_getitem__(cls, py, args);
+        }
+
+        let name = cls.name()?;
+        let (base, flags) = match args.get_item(0)?.downcast::<PyTuple>() {
+            Ok(options) if options.len() == 2 => (options.get_item(0)?, options.get_item(1)?),
+            _ => {
+                return Err(LAYOUT_ERROR.new_err(format!(
+                    "{name} must be subscripted with an unsigned integer type and its flags"
+                )))
+            }
+        };
+
+        let base = base
+            .downcast::<PyType>()
+            .ok()
+            .filter(|base| {
+                base.is_subclass_of::<Uint8>().unwrap_or(false)
+                    || base.is_subclass_of::<Uint16>().unwrap_or(false)
+                    || base.is_subclass_of::<Uint32>().unwrap_or(false)
+                    || base.is_subclass_of::<Uint64>().unwrap_or(false)
+            })
+            .ok_or_else(|| {
+                LAYOUT_ERROR.new_err(format!(
+                    "The flags of {name} must be stored in an unsigned integer type, got {base}"
+                ))
+            })?;
+
+        // Flags given as a dict become an `IntFlag` so that they behave the same either way.
+        // The same flags give the same type, so the `IntFlag` is only created once
+        let int_flag = py.import("enum")?.getattr("IntFlag")?;
+        let (names, objects) = match flags.downcast::<PyDict>() {
+            Ok(names) => (names.repr()?.to_string(), vec![base.as_ref()]),
+            Err(_) => match flags.downcast::<PyType>() {
+                Ok(flag_type) if flag_type.is_subclass(int_flag)? => {
+                    (flag_type.name()?.to_string(), vec![base.as_ref(), flags])
+                }
+                _ => {
+                    return Err(LAYOUT_ERROR.new_err(format!(
+                        "The flags of {name} must be an enum.IntFlag or a dict from names to bits, got {flags}"
+                    )))
+                }
+            },
+        };
+
+        let class_name = format!("{name}[{}, {names}]", base.name()?);
+        PyShaped::derived_type_of(cls, class_name, &objects, |class_name| {
+            let flags = match flags.downcast::<PyDict>() {
+                Ok(names) => int_flag.call1((name, names))?,
+                Err(_) => flags,
+            };
+            let new_type = py
+                .import("types")?
+                .getattr("new_class")?
+                .call1((class_name, (cls,)))?
+                .downcast::<PyType>()?;
+            new_type.setattr("__module__", cls.getattr("__module__")?)?;
+            new_type.setattr("_BASE", base)?;
+            new_type.setattr("_FLAGS", flags)?;
+            Ok(new_type.into())
+        })
+    }
+
+    /// The value as a member of the `IntFlag`, including any bits which don't belong to a flag
+    #[getter]
+    fn flags(slf: &PyCell<Self>) -> PyResult<&PyAny> {
+        let (_, flags) = Self::options(slf.get_type())?;
+        flags.call1((Self::integer(slf)?,))
+    }
+
+    /// The bits which don't belong to any flag
+    #[getter]
+    fn residual(slf: &PyCell<Self>) -> PyResult<u64> {
+        Ok(Self::split(slf.get_type(), Self::bits(slf)?)?.1)
+    }
+
+    /// Sets the flags in `value`, which writes through to the struct if this is one of its fields
+    fn add(slf: &PyCell<Self>, value: &PyAny) -> PyResult<()> {
+        let added = Self::resolve(slf.get_type(), value)?;
+        Self::store(slf, Self::integer(slf)?.call_method1("__or__", (added,))?)
+    }
+
+    /// Clears the flags in `value`, which writes through to the struct if this is one of its fields
+    fn discard(slf: &PyCell<Self>, value: &PyAny) -> PyResult<()> {
+        let removed = Self::resolve(slf.get_type(), value)?.extract::<u64>()?;
+        Self::store(
+            slf,
+            (Self::bits(slf)? & !removed)
+                .into_py(slf.py())
+                .as_ref(slf.py()),
+        )
+    }
+
+    fn __index__(slf: &PyCell<Self>) -> PyResult<&PyAny> {
+        Self::integer(slf)
+    }
+
+    fn __bool__(slf: &PyCell<Self>) -> PyResult<bool> {
+        Ok(Self::bits(slf)? != 0)
+    }
+
+    /// The number of flags which are set, not counting bits which don't belong to any flag
+    fn __len__(slf: &PyCell<Self>) -> PyResult<usize> {
+        Ok(Self::split(slf.get_type(), Self::bits(slf)?)?.0.len())
+    }
+
+    /// Iterates over the flags which are set
+    fn __iter__(slf: &PyCell<Self>) -> PyResult<&PyAny> {
+        let (set, _) = Self::split(slf.get_type(), Self::bits(slf)?)?;
+        PyTuple::new(slf.py(), set).call_method0("__iter__")
+    }
+
+    fn __contains__(slf: &PyCell<Self>, value: &PyAny) -> PyResult<bool> {
+        let bits = Self::resolve(slf.get_type(), value)?.extract::<u64>()?;
+        Ok(bits != 0 && Self::bits(slf)? & bits == bits)
+    }
+
+    fn __or__(slf: &PyCell<Self>, other: &PyAny) -> PyResult<PyObject> {
+        let py = slf.py();
+        match Self::resolve(slf.get_type(), other) {
+            Ok(other) => Ok(Self::derive(slf, Self::bits(slf)? | other.extract::<u64>()?)?.into()),
+            Err(_) => Ok(py.NotImplemented()),
+        }
+    }
+
+    fn __and__(slf: &PyCell<Self>, other: &PyAny) -> PyResult<PyObject> {
+        let py = slf.py();
+        match Self::resolve(slf.get_type(), other) {
+            Ok(other) => Ok(Self::derive(slf, Self::bits(slf)? & other.extract::<u64>()?)?.into()),
+            Err(_) => Ok(py.NotImplemented()),
+        }
+    }
+
+    fn __sub__(slf: &PyCell<Self>, other: &PyAny) -> PyResult<PyObject> {
+        let py = slf.py();
+        match Self::resolve(slf.get_type(), other) {
+            Ok(other) => Ok(Self::derive(slf, Self::bits(slf)? & !other.extract::<u64>()?)?.into()),
+            Err(_) => Ok(py.NotImplemented()),
+        }
+    }
+
+    /// Flag sets compare by their bits, both with each other and with anything they can be created from
+    fn __richcmp__(slf: &PyCell<Self>, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        let py = slf.py();
+        let other = match Self::resolve(slf.get_type(), other) {
+            Ok(other) => other,
+            Err(_) => return Ok(py.NotImplemented()),
+        };
+        match op {
+            CompareOp::Eq => Ok(Self::integer(slf)?.eq(other)?.into_py(py)),
+            CompareOp::Ne => Ok(Self::integer(slf)?.ne(other)?.into_py(py)),
+            _ => Ok(py.NotImplemented()),
+        }
+    }
+
+    /// The names of the flags which are set followed by any other bits in hex, e.g. `read|write|0x10`, or `0` if there are none
+    fn __str__(slf: &PyCell<Self>) -> PyResult<String> {
+        let (set, residual) = Self::split(slf.get_type(), Self::bits(slf)?)?;
+        let mut parts = set
+            .iter()
+            .map(|flag| flag.getattr("name")?.extract::<String>())
+            .collect::<PyResult<Vec<_>>>()?;
+        if residual != 0 {
+            parts.push(format!("{residual:#x}"));
+        }
+        match parts.is_empty() {
+            true => Ok("0".to_string()),
+            false => Ok(parts.iter().join("|")),
+        }
+    }
+
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
+        Ok(format!(
+            "{}({})",
+            slf.get_type().name()?,
+            Self::__str__(slf)?
+        ))
+    }
+
+    #[classmethod]
+    fn __packed_size__(cls: &PyType) -> PyResult<usize> {
+        Self::options(cls)?
+            .0
+            .call_method0("__packed_size__")?
+            .extract::<usize>()
+    }
+
+    #[classmethod]
+    fn __alignment__(cls: &PyType) -> PyResult<usize> {
+        Self::options(cls)?
+            .0
+            .call_method0("__alignment__")?
+            .extract::<usize>()
+    }
+
+    /// The NumPy dtype of the integer type, since NumPy has no flag sets
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        Self::options(cls)?.0.call_method1("dtype", (endianness,))
+    }
+}
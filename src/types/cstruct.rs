@@ -1,70 +1,369 @@
-use super::{Endianness, NativeEndian, PyShaped};
+use super::layout::{FieldLayout, Placements};
+use super::{
+    bytes_of, dtype_of, encode, plain_value, with_bytes, Array, CStr, Endianness, Layout, PyShaped,
+    Tagged, View,
+};
+use crate::errors::{BUFFER_SIZE_ERROR, CONSTANT_MISMATCH_ERROR, LAYOUT_ERROR};
 use pyo3::exceptions::PyAttributeError;
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyType};
-use std::cell::RefCell;
+use pyo3::types::{PyBytes, PyDict, PyList, PyString, PyTuple, PyType};
+use pyo3::{ffi, AsPyPointer};
 
 #[pyclass(module = "_cerialize", name = "cstruct", subclass, weakref, extends=PyShaped)]
 pub struct CStruct {
-    buffer: RefCell<Vec<u8>>,
-    endianness: Endianness,
+    /// The struct and the field this struct was read from, if it is the value of a dynamically sized field of another struct
+    parent: Option<(PyObject, String)>,
+}
+
+impl CStruct {
+    fn field_error(attr: &str) -> PyErr {
+        PyAttributeError::new_err(format!("Unable to locate attribute {attr}"))
+    }
+
+    /// Encodes the value of a constant field in the byte order of a struct in `endianness`, or returns `None` for any other field
+    fn constant_bytes(
+        py: Python<'_>,
+        field: &FieldLayout,
+        endianness: Endianness,
+    ) -> PyResult<Option<Vec<u8>>> {
+        match &field.constant {
+            Some(constant) => Ok(Some(encode(
+                field.type_.as_ref(py),
+                constant.as_ref(py),
+                field.endianness.unwrap_or(endianness),
+                field.overflow,
+            )?)),
+            None => Ok(None),
+        }
+    }
+
+    fn constant_error(
+        cls: &PyType,
+        field: &FieldLayout,
+        expected: &[u8],
+        actual: &[u8],
+    ) -> PyResult<PyErr> {
+        let py = cls.py();
+        let constant = match &field.constant {
+            Some(constant) => constant.as_ref(py).repr()?.to_string(),
+            None => String::new(),
+        };
+        Ok(CONSTANT_MISMATCH_ERROR.new_err(format!(
+            "The constant field {} of {} must be {constant} ({}), got {}",
+            field.name,
+            cls.name()?,
+            PyBytes::new(py, expected).repr()?,
+            PyBytes::new(py, actual).repr()?
+        )))
+    }
+
+    /// Checks that every constant field of `slf` holds the value it is declared with, including those of nested structs
+    pub(crate) fn verify_constants(slf: &PyCell<Self>) -> PyResult<()> {
+        let py = slf.py();
+        let layout = Layout::of(slf.get_type())?.get();
+        if !layout.constants() {
+            return Ok(());
+        }
+
+        let placements = Self::placements(slf, layout)?;
+        let endianness = slf.borrow().as_ref().endianness;
+        let buffer = slf.borrow().as_ref().buffer.to_vec(py);
+        for (field, &(offset, size)) in layout.fields().iter().zip(placements.fields.iter()) {
+            let actual = &buffer[offset..offset + size];
+            if let Some(expected) = Self::constant_bytes(py, field, endianness)? {
+                if actual != expected.as_slice() {
+                    return Err(Self::constant_error(
+                        slf.get_type(),
+                        field,
+                        &expected,
+                        actual,
+                    )?);
+                }
+            }
+            if !field.hidden && field.type_.as_ref(py).is_subclass_of::<Self>()? {
+                let nested = Self::__getattr__(slf, field.name.clone())?;
+                Self::verify_constants(nested.downcast::<PyCell<Self>>()?)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn padding_error(cls: &PyType, attr: &str) -> PyResult<PyErr> {
+        Ok(PyAttributeError::new_err(format!(
+            "The field {attr} of {} is padding, which can't be read or assigned",
+            cls.name()?
+        )))
+    }
+
+    /// Locates every field within the buffer of `slf`, which only requires measuring its fields if the struct is dynamically sized
+    fn placements<'a>(slf: &PyCell<Self>, layout: &'a Layout) -> PyResult<Placements<'a>> {
+        let shaped = slf.borrow();
+        let shaped = shaped.as_ref();
+        shaped.buffer.read(slf.py(), |buffer| {
+            layout.placements(slf.get_type(), buffer, shaped.endianness)
+        })
+    }
+
+    /// Returns the number of elements of `value` when it is assigned to the counted array `counted`
+    fn length_of(counted: &PyType, value: &PyAny) -> PyResult<usize> {
+        // Packed bytes hold as many elements as fit into them, anything else is a sequence of elements
+        if PyShaped::packed_bytes(value).is_none() {
+            if let Some(buffer) = bytes_of(value)? {
+                let element_type = counted.getattr("__origin__")?.downcast::<PyType>()?;
+                let element_size = Array::element_size(element_type)?;
+                if element_size == 0 || buffer.len() % element_size != 0 {
+                    return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                        "{} requires a buffer of a multiple of {element_size} bytes, got {} bytes",
+                        counted.name()?,
+                        buffer.len()
+                    )));
+                }
+                return Ok(buffer.len() / element_size);
+            }
+        }
+        value.len()
+    }
+
+    /// Follows a dotted path of (nested) fields, returning the type, offset and byte order of the field it leads to
+    fn locate<'py>(
+        cls: &'py PyType,
+        path: &str,
+        endianness: Endianness,
+    ) -> PyResult<(&'py PyType, usize, Endianness)> {
+        let py = cls.py();
+        let (mut type_, mut offset, mut endianness) = (cls, 0, endianness);
+
+        for name in path.split('.') {
+            if !type_.is_subclass_of::<Self>()? {
+                return Err(Self::field_error(path));
+            }
+            let layout = Layout::of(type_)?.get();
+            let field = layout.field(name).ok_or_else(|| Self::field_error(path))?;
+            if field.hidden {
+                return Err(Self::padding_error(type_, name)?);
+            }
+            if field.bits.is_some() {
+                return Err(LAYOUT_ERROR.new_err(format!(
+                    "The field {path} of {} is a bitfield, which doesn't have a byte offset of its own",
+                    cls.name()?
+                )));
+            }
+
+            type_ = field.type_.clone_ref(py).into_ref(py);
+            offset += field.offset;
+            endianness = field.endianness.unwrap_or(endianness);
+        }
+
+        Ok((type_, offset, endianness))
+    }
 }
 
 #[pymethods]
 impl CStruct {
+    /// Creates a zeroed instance in the byte order declared by the class (native by default), except for constant fields which hold their values.
+    /// The fields are assigned afterwards, either by `__init__` or through `from_buffer`.
+    /// Unless the class defines an `__init__` which takes them, the arguments are those of `from_buffer`: `record(buffer, endianness)` decodes `buffer`
     #[new]
-    fn new(buffer: &[u8], endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        (
-            Self {
-                buffer: RefCell::new(Vec::from(buffer)),
-                endianness,
-            },
-            PyShaped::new(),
-        )
+    #[classmethod]
+    #[pyo3(signature = (*args, **kwargs))]
+    pub(crate) fn new(
+        cls: &PyType,
+        args: &PyTuple,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let py = cls.py();
+        // Arguments are left to an `__init__` defined by the class (such as the one `@cstruct` generates), anything else is decoded
+        let defines_init = !cls
+            .getattr("__init__")?
+            .is(py.get_type::<PyAny>().getattr("__init__")?);
+        if !defines_init && (!args.is_empty() || kwargs.is_some_and(|kwargs| !kwargs.is_empty())) {
+            let decoded = cls
+                .call_method("from_buffer", args, kwargs)?
+                .downcast::<PyCell<PyShaped>>()?
+                .borrow();
+            return Ok((
+                Self { parent: None },
+                PyShaped::with_view(decoded.buffer.clone(), decoded.endianness),
+            ));
+        }
+
+        let layout = Layout::of(cls)?.get();
+        let endianness = layout.endianness().unwrap_or_default();
+        let mut buffer = vec![0; layout.size()];
+
+        // A new instance has the layout of an empty one, so every field is at the offset the layout was compiled with
+        if layout.constants() {
+            for field in layout.fields() {
+                let type_ = field.type_.as_ref(py);
+                let constant = match Self::constant_bytes(py, field, endianness)? {
+                    Some(constant) => constant,
+                    None if type_.is_subclass_of::<Self>()? => encode(
+                        type_,
+                        type_.call_method1("__new__", (type_,))?,
+                        field.endianness.unwrap_or(endianness),
+                        None,
+                    )?,
+                    None => continue,
+                };
+                buffer[field.offset..field.offset + constant.len()].copy_from_slice(&constant);
+            }
+        }
+
+        Ok((
+            Self { parent: None },
+            PyShaped::with_buffer(py, buffer, endianness)?,
+        ))
     }
 
+    #[classmethod]
+    fn __layout__(cls: &PyType) -> PyResult<&PyCell<Layout>> {
+        Layout::of(cls)
+    }
+
+    /// The size of every instance, which only exists if none of the fields is dynamically sized
     #[classmethod]
     fn __packed_size__(cls: &PyType) -> PyResult<usize> {
-        let mut capacity = 0_usize;
-        // This assumes that there are no padding bytes
-        // That should probably be fixed at some point
-        let fields = cls.getattr("_CFIELDS")?.downcast::<PyDict>()?;
-        for (_, value) in fields {
-            capacity += value.call_method0("__packed_size__")?.extract::<usize>()?;
+        let layout = Layout::of(cls)?.get();
+        if layout.dynamic() {
+            return Err(LAYOUT_ERROR.new_err(format!(
+                "{} is dynamically sized, the size of an instance depends on the values of its fields",
+                cls.name()?
+            )));
         }
-        Ok(capacity)
+        Ok(layout.size())
     }
 
-    fn _type_and_offset_of(slf: &PyCell<Self>, attr: String) -> PyResult<(&PyType, usize)> {
-        let fields = slf.getattr("_CFIELDS")?.downcast::<PyDict>()?;
-        let mut attr_type = None;
+    #[classmethod]
+    fn __alignment__(cls: &PyType) -> PyResult<usize> {
+        Ok(Layout::of(cls)?.get().alignment())
+    }
 
-        let offset = fields
-            .iter()
-            .map_while(|(key, value)| {
-                let cur = key.extract::<&str>().unwrap();
-                match cur == attr {
-                    false => Some(
-                        value
-                            .call_method0("__packed_size__")
-                            .unwrap()
-                            .extract::<usize>()
-                            .unwrap(),
-                    ),
-                    true => {
-                        attr_type = Some(value.downcast::<PyType>().unwrap());
-                        None
-                    }
+    /// Returns the equivalent NumPy structured dtype, in the declared byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let py = cls.py();
+        let layout = Layout::of(cls)?.get();
+        let endianness = endianness.or(layout.endianness()).unwrap_or_default();
+
+        let (names, formats, offsets) = (PyList::empty(py), PyList::empty(py), PyList::empty(py));
+        // Padding is left out, the offsets of the other fields and the item size account for it
+        for field in layout.fields().iter().filter(|field| !field.hidden) {
+            if field.bits.is_some() {
+                return Err(LAYOUT_ERROR.new_err(format!(
+                    "{} has no NumPy dtype since {} is a bitfield",
+                    cls.name()?,
+                    field.name
+                )));
+            }
+            names.append(&field.name)?;
+            formats.append(dtype_of(
+                field.type_.as_ref(py),
+                field.endianness.unwrap_or(endianness),
+            )?)?;
+            offsets.append(field.offset)?;
+        }
+
+        let spec = PyDict::new(py);
+        spec.set_item("names", names)?;
+        spec.set_item("formats", formats)?;
+        spec.set_item("offsets", offsets)?;
+        spec.set_item("itemsize", Self::__packed_size__(cls)?)?;
+        py.import("numpy")?.getattr("dtype")?.call1((spec,))
+    }
+
+    /// Decodes the field at `path` (e.g. `"header.timestamp"`) of every record packed back to back in `buffer`.
+    /// The values are returned as one array in native byte order, e.g. a `u32[N]` for a `u32` field of `N` records.
+    /// Trailing bytes which don't make up a whole record raise a `BufferSizeError` when `strict` and are ignored otherwise.
+    /// The GIL is released while the values are copied, so other threads keep running.
+    #[classmethod]
+    #[pyo3(signature = (buffer, path, endianness = None, *, strict = true))]
+    fn extract_column<'py>(
+        cls: &'py PyType,
+        buffer: &PyAny,
+        path: &str,
+        endianness: Option<Endianness>,
+        strict: bool,
+    ) -> PyResult<&'py PyAny> {
+        let py = cls.py();
+        let layout = Layout::of(cls)?.get();
+        let endianness = endianness.or(layout.endianness()).unwrap_or_default();
+        let (type_, offset, endianness) = Self::locate(cls, path, endianness)?;
+
+        // Only primitives (and arrays of them) have a single width that their bytes can be swapped by
+        let (element_type, shape) = match type_.is_subclass_of::<Array>()? {
+            true => Array::dimensions(type_)?,
+            false => (type_, Vec::new()),
+        };
+        if element_type.is_subclass_of::<Self>()? || element_type.is_subclass_of::<Array>()? {
+            return Err(LAYOUT_ERROR.new_err(format!(
+                "The field {path} of {} must be a primitive or an array of primitives to be extracted, got {}",
+                cls.name()?,
+                type_.name()?
+            )));
+        }
+        // Characters are single bytes regardless of the length of the string
+        let width = match element_type.is_subclass_of::<CStr>()? {
+            true => 1,
+            false => element_type
+                .call_method0("__packed_size__")?
+                .extract::<usize>()?,
+        };
+        let size = type_.call_method0("__packed_size__")?.extract::<usize>()?;
+        let stride = Self::__packed_size__(cls)?;
+        if stride == 0 {
+            return Err(LAYOUT_ERROR.new_err(format!(
+                "{} has no packed size, so a buffer can't be split into its records",
+                cls.name()?
+            )));
+        }
+
+        let (column, count) = with_bytes(buffer, |bytes| {
+            if strict && bytes.len() % stride != 0 {
+                return Err(bytes.len());
+            }
+
+            let swap = !endianness.is_native() && width > 1;
+            let count = bytes.len() / stride;
+            Ok(py.allow_threads(|| {
+                let mut column = Vec::with_capacity(count * size);
+                for record in bytes.chunks_exact(stride) {
+                    column.extend_from_slice(&record[offset..offset + size]);
+                }
+                if swap {
+                    column
+                        .chunks_exact_mut(width)
+                        .for_each(|value| value.reverse());
                 }
-            })
-            .sum();
+                (column, count)
+            }))
+        })?
+        .map_err(|len| {
+            BUFFER_SIZE_ERROR.new_err(format!(
+                "{} requires a buffer of a multiple of {stride} bytes, got {len} bytes",
+                cls.name().unwrap_or("The struct")
+            ))
+        })?;
+
+        let shape = PyTuple::new(py, [&[count], shape.as_slice()].concat());
+        let column_type =
+            PyShaped::__class_getitem__(element_type, py, PyTuple::new(py, [shape]))?.into_ref(py);
+        PyShaped::decode(
+            column_type,
+            View::new(py, column)?,
+            Some(Endianness::Native),
+        )
+    }
 
-        match attr_type {
-            Some(type_) => Ok((type_, offset)),
-            None => Err(PyAttributeError::new_err(format!(
-                "Unable to locate attribute {attr}"
-            ))),
+    fn _type_and_offset_of(slf: &PyCell<Self>, attr: String) -> PyResult<(&PyType, usize)> {
+        let layout = Layout::of(slf.get_type())?.get();
+
+        match (layout.field(&attr), layout.position(&attr)) {
+            (Some(field), Some(position)) => Ok((
+                field.type_.as_ref(slf.py()),
+                Self::placements(slf, layout)?.fields[position].0,
+            )),
+            _ => Err(Self::field_error(&attr)),
         }
     }
 
@@ -77,18 +376,325 @@ impl CStruct {
     }
 
     fn __getattr__(slf: &PyCell<Self>, attr: String) -> PyResult<&PyAny> {
-        let (attr_type, buffer_offset) = Self::_type_and_offset_of(slf, attr)?;
-        let type_size = attr_type
-            .call_method0("__packed_size__")?
-            .extract::<usize>()?;
-
-        attr_type.call_method1(
-            "__new__",
-            (
-                attr_type,
-                &slf.borrow().buffer.borrow()[buffer_offset..buffer_offset + type_size],
-                Some(slf.borrow().endianness),
-            ),
-        )
+        let py = slf.py();
+        let layout = Layout::of(slf.get_type())?.get();
+        let (Some(field), Some(position)) = (layout.field(&attr), layout.position(&attr)) else {
+            return Err(Self::field_error(&attr));
+        };
+        if field.hidden {
+            return Err(Self::padding_error(slf.get_type(), &attr)?);
+        }
+        let placements = Self::placements(slf, layout)?;
+        let (offset, size) = placements.fields[position];
+        let this = slf.borrow();
+        let shaped = this.as_ref();
+        let view = shaped.buffer.slice(offset, size);
+        let endianness = field.endianness.unwrap_or(shaped.endianness);
+
+        // Bitfields are read as plain integers since they have no type of their own
+        if let Some(bits) = field.bits {
+            let value = view.read(py, |buffer| bits.read(buffer, endianness));
+            return Ok(value.into_py(py).into_ref(py));
+        }
+
+        // A counted array is read as an array of as many elements as its count says, and a tagged union as the variant its tag selects
+        let type_ = match (field.count, field.tag) {
+            (Some(count), _) => {
+                let length = shaped.buffer.read(py, |buffer| {
+                    layout.count(
+                        py,
+                        count,
+                        placements.fields[count],
+                        buffer,
+                        shaped.endianness,
+                    )
+                })?;
+                Array::with_length(field.type_.as_ref(py), length)?
+            }
+            (_, Some(tag)) => {
+                let variant = shaped.buffer.read(py, |buffer| {
+                    layout.variant(
+                        field.type_.as_ref(py),
+                        tag,
+                        placements.fields[tag],
+                        buffer,
+                        shaped.endianness,
+                    )
+                })?;
+                match variant {
+                    Some(variant) => variant,
+                    // Nothing but its bytes is known about a payload with an unknown tag
+                    None => return Ok(PyBytes::new(py, &view.to_vec(py))),
+                }
+            }
+            _ => field.type_.as_ref(py),
+        };
+
+        // Strings, byte strings and enums are read as `str`, `bytes` and members since their contents are all there is to them
+        if let Some(value) = view.read(py, |buffer| plain_value(type_, py, buffer, endianness))? {
+            return Ok(value);
+        }
+
+        // The value is a view into this struct's buffer rather than a copy of it
+        let value = PyShaped::from_view(type_, view, endianness)?;
+        // A dynamically sized struct remembers where it was read from, so that it can make room for itself when it changes size
+        if field.dynamic {
+            if let Ok(nested) = value.downcast::<PyCell<Self>>() {
+                nested.borrow_mut().parent = Some((slf.into(), attr));
+            }
+        }
+        Ok(value)
+    }
+
+    /// Assigns a field, encoding `value` in the byte order of the field.
+    /// Assigning a counted array updates its count, and assigning a count truncates (or zero-extends) the arrays it counts.
+    /// Likewise, assigning a tagged union updates its tag, and assigning a tag replaces the payload with an empty value of the variant it selects.
+    /// A dynamically sized field which changes size moves the fields after it, which reallocates the struct:
+    /// views of its fields, buffers exported from it and the buffer it was decoded from no longer see later modifications.
+    /// A struct read from a field of another struct resizes that field as well, so the modification is seen by the struct it is nested in.
+    fn __setattr__(slf: &PyCell<Self>, attr: &str, value: &PyAny) -> PyResult<()> {
+        let py = slf.py();
+        let layout = Layout::of(slf.get_type())?.get();
+        let (Some(field), Some(position)) = (layout.field(attr), layout.position(attr)) else {
+            // Anything which isn't a field is stored the same way as on any other object
+            let name = PyString::new(py, attr);
+            let result = unsafe {
+                ffi::PyObject_GenericSetAttr(slf.as_ptr(), name.as_ptr(), value.as_ptr())
+            };
+            return match result {
+                0 => Ok(()),
+                _ => Err(PyErr::fetch(py)),
+            };
+        };
+        if field.hidden {
+            return Err(Self::padding_error(slf.get_type(), attr)?);
+        }
+
+        let struct_endianness = slf.borrow().as_ref().endianness;
+        let endianness = field.endianness.unwrap_or(struct_endianness);
+        let placements = Self::placements(slf, layout)?;
+        // The buffer is only copied if the struct has to be repacked, anything else reads from it directly
+        let buffer = slf.borrow().as_ref().buffer.clone();
+        let type_ = field.type_.as_ref(py);
+
+        // A constant can only be assigned the value it already holds
+        if let Some(expected) = Self::constant_bytes(py, field, struct_endianness)? {
+            let encoded = encode(type_, value, endianness, field.overflow)?;
+            if encoded != expected {
+                return Err(Self::constant_error(
+                    slf.get_type(),
+                    field,
+                    &expected,
+                    &encoded,
+                )?);
+            }
+        }
+
+        // Only the bits of a bitfield change, the rest of its storage unit is left as it is
+        if let Some(bits) = field.bits {
+            let (offset, size) = placements.fields[position];
+            let overflow = field.overflow.unwrap_or_default();
+            let unit = bits.write(
+                attr,
+                &buffer.slice(offset, size).to_vec(py),
+                value,
+                endianness,
+                overflow,
+            )?;
+            buffer.write(py, offset, &unit);
+            return Ok(());
+        }
+
+        // The new contents of every field which changes, which is more than one if the field is (or holds) the length or tag of another
+        let mut replacements = Vec::new();
+        match (field.count, field.tag) {
+            (Some(count), _) => {
+                let length = Self::length_of(type_, value)?;
+                let encoded = encode(
+                    Array::with_length(type_, length)?,
+                    value,
+                    endianness,
+                    field.overflow,
+                )?;
+                replacements.push((position, encoded));
+
+                let count_field = &layout.fields()[count];
+                let length = length.into_py(py);
+                let encoded = encode(
+                    count_field.type_.as_ref(py),
+                    length.as_ref(py),
+                    count_field.endianness.unwrap_or(struct_endianness),
+                    count_field.overflow,
+                )?;
+                replacements.push((count, encoded));
+            }
+            (_, Some(tag)) => {
+                let current = buffer.read(py, |buffer| {
+                    layout.variant(
+                        type_,
+                        tag,
+                        placements.fields[tag],
+                        buffer,
+                        struct_endianness,
+                    )
+                })?;
+                // Raw bytes can only replace the payload of an unknown tag, any other value selects the variant it is an instance of
+                let raw = match current {
+                    None if PyShaped::packed_bytes(value).is_none() => bytes_of(value)?,
+                    _ => None,
+                };
+                match raw {
+                    Some(raw) => replacements.push((position, raw)),
+                    None => {
+                        let (tag_value, variant) = Tagged::tag_for(type_, value)?;
+                        let encoded = encode(variant, value, endianness, field.overflow)?;
+                        replacements.push((position, encoded));
+
+                        let tag_field = &layout.fields()[tag];
+                        let encoded = encode(
+                            tag_field.type_.as_ref(py),
+                            tag_value,
+                            tag_field.endianness.unwrap_or(struct_endianness),
+                            tag_field.overflow,
+                        )?;
+                        replacements.push((tag, encoded));
+                    }
+                }
+            }
+            _ => {
+                let encoded = encode(type_, value, endianness, field.overflow)?;
+                let size = placements.fields[position].1;
+                if !field.dynamic && encoded.len() != size {
+                    return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                        "The field {attr} of {} is {size} bytes large, got {} bytes",
+                        slf.get_type().name()?,
+                        encoded.len()
+                    )));
+                }
+
+                for (index, counted) in layout.fields().iter().enumerate() {
+                    if counted.count != Some(position) {
+                        continue;
+                    }
+                    let length = layout.count(
+                        py,
+                        position,
+                        (0, encoded.len()),
+                        &encoded,
+                        struct_endianness,
+                    )?;
+                    let element_type = counted.type_.as_ref(py).getattr("__origin__")?;
+                    let element_size = Array::element_size(element_type.downcast::<PyType>()?)?;
+                    let (offset, size) = placements.fields[index];
+                    let Some(new_size) = length.checked_mul(element_size) else {
+                        return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                            "The field {} of {} can't hold {length} elements",
+                            counted.name,
+                            slf.get_type().name()?
+                        )));
+                    };
+                    let mut elements = buffer.slice(offset, size).to_vec(py);
+                    elements.resize(new_size, 0);
+                    replacements.push((index, elements));
+                }
+
+                for (index, tagged) in layout.fields().iter().enumerate() {
+                    if tagged.tag != Some(position) {
+                        continue;
+                    }
+                    let tagged_type = tagged.type_.as_ref(py);
+                    let current = buffer.read(py, |buffer| {
+                        layout.variant(
+                            tagged_type,
+                            position,
+                            placements.fields[position],
+                            buffer,
+                            struct_endianness,
+                        )
+                    })?;
+                    let selected = layout.variant(
+                        tagged_type,
+                        position,
+                        (0, encoded.len()),
+                        &encoded,
+                        struct_endianness,
+                    )?;
+                    // The payload is kept if its variant doesn't change, or if the new tag is unknown
+                    match selected {
+                        Some(variant) if !current.is_some_and(|current| current.is(variant)) => {
+                            let empty = encode(
+                                variant,
+                                variant.call_method1("__new__", (variant,))?,
+                                tagged.endianness.unwrap_or(struct_endianness),
+                                None,
+                            )?;
+                            replacements.push((index, empty));
+                        }
+                        _ => {}
+                    }
+                }
+                replacements.insert(0, (position, encoded));
+            }
+        }
+
+        // The struct keeps its memory unless a field changes size
+        if replacements
+            .iter()
+            .all(|(index, encoded)| encoded.len() == placements.fields[*index].1)
+        {
+            for (index, encoded) in replacements {
+                buffer.write(py, placements.fields[index].0, &encoded);
+            }
+            return Ok(());
+        }
+
+        let packed = buffer.to_vec(py);
+        let mut values = placements
+            .fields
+            .iter()
+            .map(|&(offset, size)| packed[offset..offset + size].to_vec())
+            .collect::<Vec<_>>();
+        for (index, encoded) in replacements {
+            values[index] = encoded;
+        }
+        // A struct read from another one grows or shrinks within it, as long as it is still part of the other's memory
+        let parent = slf.borrow().parent.clone();
+        let parent = match parent {
+            Some((parent, attr)) => {
+                let parent = parent.into_ref(py).downcast::<PyCell<Self>>()?;
+                let attached = parent.borrow().as_ref().buffer.shares_storage(&buffer);
+                attached.then_some((parent, attr))
+            }
+            None => None,
+        };
+        slf.borrow_mut().as_mut().buffer = View::new(py, layout.pack(&values))?;
+        if let Some((parent, attr)) = parent {
+            parent.setattr(attr.as_str(), slf)?;
+            let moved = parent.getattr(attr.as_str())?;
+            let buffer = moved
+                .downcast::<PyCell<PyShaped>>()?
+                .borrow()
+                .buffer
+                .clone();
+            slf.borrow_mut().as_mut().buffer = buffer;
+        }
+        Ok(())
+    }
+
+    fn __delattr__(slf: &PyCell<Self>, attr: &str) -> PyResult<()> {
+        if Layout::of(slf.get_type())?.get().field(attr).is_some() {
+            return Err(PyAttributeError::new_err(format!(
+                "The field {attr} can't be deleted"
+            )));
+        }
+
+        let name = PyString::new(slf.py(), attr);
+        let result = unsafe {
+            ffi::PyObject_GenericSetAttr(slf.as_ptr(), name.as_ptr(), std::ptr::null_mut())
+        };
+        match result {
+            0 => Ok(()),
+            _ => Err(PyErr::fetch(slf.py())),
+        }
     }
 }
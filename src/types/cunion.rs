@@ -0,0 +1,28 @@
+use super::CStruct;
+use pyo3::prelude::*;
+use pyo3::pyclass_init::PyClassInitializer;
+use pyo3::types::{PyDict, PyTuple, PyType};
+
+/// A C `union`, whose members all start at the beginning of one shared buffer.
+/// It is declared the same way as a `cstruct` and reads and writes its members the same way too, only the layout differs:
+/// the union is as large as its largest member (rounded up to its alignment), so assigning one member changes the others.
+#[pyclass(module = "_cerialize", name = "cunion", subclass, extends=CStruct)]
+pub struct CUnion();
+
+#[pymethods]
+impl CUnion {
+    /// Creates a zeroed instance in the byte order declared by the class (native by default)
+    #[new]
+    #[classmethod]
+    #[pyo3(signature = (*args, **kwargs))]
+    fn new(
+        cls: &PyType,
+        args: &PyTuple,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<PyClassInitializer<Self>> {
+        let (cstruct, shaped) = CStruct::new(cls, args, kwargs)?;
+        Ok(PyClassInitializer::from(shaped)
+            .add_subclass(cstruct)
+            .add_subclass(Self()))
+    }
+}
@@ -0,0 +1,142 @@
+use super::{
+    bytes_of, is_dynamic, measure, Array, CStruct, CUnion, Endianness, Layout, Overflow,
+    PrefixedBytes, PyShaped,
+};
+use crate::errors::{BUFFER_SIZE_ERROR, SHAPE_ERROR};
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyType};
+
+/// Encodes `value` into the representation used by a field of type `type_` in a struct with the given byte order.
+/// `overflow` overrides how integers which don't fit into the field are handled.
+pub fn encode(
+    type_: &PyType,
+    value: &PyAny,
+    endianness: Endianness,
+    overflow: Option<Overflow>,
+) -> PyResult<Vec<u8>> {
+    // Values which already have the right type and byte order can be copied as is
+    if value.is_instance(type_)? {
+        if let Some((buffer, value_endianness)) = PyShaped::packed_bytes(value) {
+            if value_endianness == endianness {
+                return Ok(buffer);
+            }
+        }
+    }
+
+    // Any other bytes-like object is taken to already be in the packed representation, except by `bytes_prefixed` which holds bytes.
+    // The contents of a dynamically sized value have to be exactly one value though, e.g. a `cstring` has to end with its terminator
+    if PyShaped::packed_bytes(value).is_none() && !type_.is_subclass_of::<PrefixedBytes>()? {
+        if let Some(buffer) = bytes_of(value)? {
+            let size = match is_dynamic(type_)? {
+                true => measure(type_, &buffer, endianness)?,
+                false => buffer.len(),
+            };
+            if size != buffer.len() {
+                return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                    "{} requires a buffer of exactly {size} bytes, got {}",
+                    type_.name()?,
+                    buffer.len()
+                )));
+            }
+            return Ok(buffer);
+        }
+    }
+
+    if type_.is_subclass_of::<CStruct>()? {
+        if !value.is_instance(type_)? {
+            return Err(PyTypeError::new_err(format!(
+                "Expected an instance of {} or a bytes-like object, got {}",
+                type_.name()?,
+                value.get_type().name()?
+            )));
+        }
+
+        // Which member of a union is in use isn't known, so its bytes are copied as they are instead of being converted
+        if type_.is_subclass_of::<CUnion>()? {
+            return packed_bytes(value);
+        }
+
+        // The byte order differs so every field has to be encoded again
+        let target = type_.call_method1("__new__", (type_,))?;
+        target
+            .downcast::<PyCell<PyShaped>>()?
+            .borrow_mut()
+            .endianness = endianness;
+        let layout = Layout::of(type_)?.get();
+        for field in layout.fields().iter().filter(|field| !field.hidden) {
+            target.setattr(field.name.as_str(), value.getattr(field.name.as_str())?)?;
+        }
+
+        // Padding doesn't have a byte order, so its bytes are copied as they are
+        if let Some((source, source_endianness)) = PyShaped::packed_bytes(value) {
+            let placements = layout.placements(type_, &source, source_endianness)?;
+            let target = target.downcast::<PyCell<PyShaped>>()?.borrow();
+            for (field, &(offset, size)) in layout.fields().iter().zip(placements.fields.iter()) {
+                if field.hidden {
+                    target
+                        .buffer
+                        .write(type_.py(), offset, &source[offset..offset + size]);
+                }
+            }
+        }
+        return packed_bytes(target);
+    }
+
+    // Arrays are assigned as a whole from a (nested) sequence with one value for each element
+    if type_.is_subclass_of::<Array>()? {
+        let (element_type, shape) = Array::dimensions(type_)?;
+        let mut buffer = Vec::new();
+        encode_elements(
+            element_type,
+            value,
+            &shape,
+            endianness,
+            overflow,
+            &mut buffer,
+        )?;
+        return Ok(buffer);
+    }
+
+    // Only pass the policy along when there is one since only the integer types accept it
+    let kwargs = overflow
+        .map(|overflow| [("overflow", overflow.into_py(type_.py()))].into_py_dict(type_.py()));
+    let instance = type_.call((value, endianness), kwargs)?;
+    packed_bytes(instance)
+}
+
+fn encode_elements(
+    element_type: &PyType,
+    value: &PyAny,
+    dimensions: &[usize],
+    endianness: Endianness,
+    overflow: Option<Overflow>,
+    buffer: &mut Vec<u8>,
+) -> PyResult<()> {
+    let Some((&length, inner)) = dimensions.split_first() else {
+        buffer.extend(encode(element_type, value, endianness, overflow)?);
+        return Ok(());
+    };
+
+    if value.len()? != length {
+        return Err(SHAPE_ERROR.new_err(format!(
+            "Expected {length} elements for an array of {}, got {}",
+            element_type.name()?,
+            value.len()?
+        )));
+    }
+    for element in value.iter()? {
+        encode_elements(element_type, element?, inner, endianness, overflow, buffer)?;
+    }
+    Ok(())
+}
+
+fn packed_bytes(value: &PyAny) -> PyResult<Vec<u8>> {
+    match PyShaped::packed_bytes(value) {
+        Some((buffer, _)) => Ok(buffer),
+        None => Err(PyTypeError::new_err(format!(
+            "{} is not a shaped type",
+            value.get_type().name()?
+        ))),
+    }
+}
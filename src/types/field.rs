@@ -0,0 +1,141 @@
+use super::{Endianness, Overflow, PyShaped};
+use crate::errors::LAYOUT_ERROR;
+use pyo3::prelude::*;
+use pyo3::types::PyType;
+
+use itertools::Itertools;
+
+/// The declaration of a struct field together with the options which change how values are encoded into it.
+/// A plain type in `_CFIELDS` is the same as a `field` without any options.
+#[pyclass(module = "_cerialize", name = "field", frozen)]
+pub struct Field {
+    pub type_: Py<PyType>,
+    pub overflow: Option<Overflow>,
+    /// Takes precedence over the byte order of the struct the field is part of
+    pub endianness: Option<Endianness>,
+    /// The width of a bitfield, which is packed into a storage unit of the field's type together with the bitfields next to it
+    pub bits: Option<u32>,
+    /// Where the field starts within the struct, instead of right after the field before it
+    pub offset: Option<usize>,
+    /// The value the field always holds, e.g. the magic number at the start of a file, which decoding checks the buffer against
+    pub constant: Option<PyObject>,
+}
+
+impl Field {
+    /// Extracts the type and options of an entry of `_CFIELDS`
+    pub fn declared(name: &str, cls: &PyType, value: &PyAny) -> PyResult<Self> {
+        if let Ok(field) = value.downcast::<PyCell<Self>>() {
+            let field = field.get();
+            return Ok(Self {
+                type_: field.type_.clone_ref(value.py()),
+                overflow: field.overflow,
+                endianness: field.endianness,
+                bits: field.bits,
+                offset: field.offset,
+                constant: field
+                    .constant
+                    .as_ref()
+                    .map(|constant| constant.clone_ref(value.py())),
+            });
+        }
+
+        match value.downcast::<PyType>() {
+            Ok(type_) if type_.is_subclass_of::<PyShaped>()? => Ok(Self {
+                type_: type_.into(),
+                overflow: None,
+                endianness: None,
+                bits: None,
+                offset: None,
+                constant: None,
+            }),
+            _ => Err(LAYOUT_ERROR.new_err(format!(
+                "The field {name} of {} must be a cerialize type, got {value:?}",
+                cls.name()?
+            ))),
+        }
+    }
+}
+
+#[pymethods]
+impl Field {
+    #[new]
+    #[pyo3(signature = (type_, *, overflow = None, endianness = None, bits = None, offset = None, constant = None))]
+    fn new(
+        type_: &PyType,
+        overflow: Option<Overflow>,
+        endianness: Option<Endianness>,
+        bits: Option<u32>,
+        offset: Option<usize>,
+        constant: Option<PyObject>,
+    ) -> PyResult<Self> {
+        if !type_.is_subclass_of::<PyShaped>()? {
+            return Err(LAYOUT_ERROR.new_err(format!(
+                "A field must have a cerialize type, got {}",
+                type_.name()?
+            )));
+        }
+
+        Ok(Self {
+            type_: type_.into(),
+            overflow,
+            endianness,
+            bits,
+            offset,
+            constant,
+        })
+    }
+
+    #[getter(type)]
+    fn py_type(&self, py: Python<'_>) -> Py<PyType> {
+        self.type_.clone_ref(py)
+    }
+
+    #[getter(overflow)]
+    fn py_overflow(&self) -> Option<Overflow> {
+        self.overflow
+    }
+
+    #[getter(endianness)]
+    fn py_endianness(&self) -> Option<Endianness> {
+        self.endianness
+    }
+
+    #[getter(bits)]
+    fn py_bits(&self) -> Option<u32> {
+        self.bits
+    }
+
+    #[getter(offset)]
+    fn py_offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    #[getter(constant)]
+    fn py_constant(&self, py: Python<'_>) -> Option<PyObject> {
+        self.constant
+            .as_ref()
+            .map(|constant| constant.clone_ref(py))
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let options = [
+            self.overflow
+                .map(|overflow| format!("overflow=Overflow.{}", overflow.name())),
+            self.endianness
+                .map(|endianness| format!("endianness=Endianness.{}", endianness.name())),
+            self.bits.map(|bits| format!("bits={bits}")),
+            self.offset.map(|offset| format!("offset={offset:#x}")),
+            match &self.constant {
+                Some(constant) => Some(format!("constant={}", constant.as_ref(py).repr()?)),
+                None => None,
+            },
+        ];
+
+        Ok(format!(
+            "field({})",
+            std::iter::once(self.type_.as_ref(py).name()?.to_string())
+                .chain(options.into_iter().flatten())
+                .format(", ")
+        ))
+    }
+}
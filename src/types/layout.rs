@@ -0,0 +1,631 @@
+use super::{
+    encode, Array, BitAllocator, BitOrder, Bits, CString, CStruct, CUnion, Endianness, Field,
+    Overflow, Pad, PascalString, PrefixedBytes, Tagged,
+};
+use crate::errors::{BUFFER_SIZE_ERROR, LAYOUT_ERROR};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyType};
+
+use itertools::Itertools;
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Rounds `offset` up to the closest multiple of `alignment`
+pub fn align_to(offset: usize, alignment: usize) -> usize {
+    offset.div_ceil(alignment) * alignment
+}
+
+/// Whether the size of values of `type_` depends on their contents, such as that of a `cstring`
+pub fn is_dynamic(type_: &PyType) -> PyResult<bool> {
+    if type_.is_subclass_of::<CString>()?
+        || type_.is_subclass_of::<PrefixedBytes>()?
+        || type_.is_subclass_of::<PascalString>()?
+    {
+        return Ok(true);
+    }
+    if type_.is_subclass_of::<CStruct>()? {
+        return Ok(Layout::of(type_)?.get().dynamic);
+    }
+    if type_.is_subclass_of::<Array>()? {
+        return Ok(Array::count_of(type_)?.is_some());
+    }
+    if type_.is_subclass_of::<Tagged>()? {
+        return Ok(Tagged::tag_of(type_)?.is_some());
+    }
+    Ok(false)
+}
+
+/// Returns the size of the value of type `type_` which starts at the beginning of `buffer` and is stored in the given byte order
+pub fn measure(type_: &PyType, buffer: &[u8], endianness: Endianness) -> PyResult<usize> {
+    if type_.is_subclass_of::<CString>()? {
+        return CString::measure(type_, buffer);
+    }
+    if type_.is_subclass_of::<PrefixedBytes>()? {
+        return PrefixedBytes::measure(type_, buffer, endianness);
+    }
+    if type_.is_subclass_of::<PascalString>()? {
+        return PascalString::measure(type_, buffer, endianness);
+    }
+    if type_.is_subclass_of::<CStruct>()? {
+        let layout = Layout::of(type_)?.get();
+        if layout.dynamic {
+            return Ok(layout.placements(type_, buffer, endianness)?.size);
+        }
+    }
+    type_.call_method0("__packed_size__")?.extract::<usize>()
+}
+
+/// The byte order which instances of `type_` are created in unless another one is given
+pub fn declared_endianness(type_: &PyType) -> PyResult<Endianness> {
+    match type_.is_subclass_of::<CStruct>()? {
+        true => Ok(Layout::of(type_)?.get().endianness.unwrap_or_default()),
+        false => Ok(Endianness::default()),
+    }
+}
+
+/// The location of a single field within its struct
+pub struct FieldLayout {
+    pub name: String,
+    pub type_: Py<PyType>,
+    /// The offset of the field in an instance where every dynamically sized field has its smallest size
+    pub offset: usize,
+    /// The size of the field, the smallest one it can have if it is dynamically sized
+    pub size: usize,
+    pub alignment: usize,
+    /// Whether the size of the field depends on its value, in which case the offsets of the fields after it do as well
+    pub dynamic: bool,
+    /// The position of the field which holds the number of elements of a counted array, e.g. `count` for `Item["count"]`
+    pub count: Option<usize>,
+    /// The position of the field which selects the variant of a tagged union, e.g. `kind` for `tagged["kind", {...}]`
+    pub tag: Option<usize>,
+    /// Where a bitfield is stored within its storage unit, which is what the offset and size of the field refer to
+    pub bits: Option<Bits>,
+    /// How integers which don't fit into the field are handled, defaulting to the policy of the field's type
+    pub overflow: Option<Overflow>,
+    /// The byte order of the field if it differs from the struct's
+    pub endianness: Option<Endianness>,
+    /// Whether the offset was declared with the field rather than following from the fields before it
+    pub explicit_offset: bool,
+    /// Whether the field is padding (`pad` or `reserved`), which only takes up space and can't be read or assigned
+    pub hidden: bool,
+    /// The value the field always holds, which new instances start out with and decoded ones are checked against
+    pub constant: Option<PyObject>,
+}
+
+/// The compiled layout of a struct.
+/// This is computed once per class and stored on it so that looking up a field doesn't have to walk every preceding field.
+#[pyclass(module = "_cerialize", name = "Layout", frozen)]
+pub struct Layout {
+    fields: Vec<FieldLayout>,
+    index: HashMap<String, usize>,
+    size: usize,
+    alignment: usize,
+    /// The byte order declared through `_ENDIANNESS`, which new instances default to
+    endianness: Option<Endianness>,
+    /// Whether any field is dynamically sized, in which case the offsets are computed for each instance
+    dynamic: bool,
+    /// The offset and size of every field, which are where the fields of every instance are unless the layout is dynamic
+    placements: Vec<(usize, usize)>,
+    /// Whether any field is a constant or a nested struct which has constant fields
+    constants: bool,
+}
+
+/// The offset and size of every field of one instance, which are only computed for dynamically sized structs
+pub struct Placements<'a> {
+    pub fields: Cow<'a, [(usize, usize)]>,
+    pub size: usize,
+}
+
+impl Layout {
+    /// Computes the offset of every field the same way a C compiler would.
+    /// Each field is placed at the next offset which satisfies its natural alignment, unless the struct is packed.
+    /// Every member of a union is placed at offset 0 instead, so the union is as large as its largest member.
+    pub fn compile(cls: &PyType) -> PyResult<Self> {
+        let name = cls.name()?;
+        let union = cls.is_subclass_of::<CUnion>()?;
+        let packed = match cls.getattr("_PACKED") {
+            Ok(value) => value.is_true()?,
+            Err(_) => false,
+        };
+        let minimum_alignment = match cls.getattr("_ALIGNMENT") {
+            Ok(value) => value.extract::<usize>()?,
+            Err(_) => 1,
+        };
+        if !minimum_alignment.is_power_of_two() {
+            return Err(LAYOUT_ERROR.new_err(format!(
+                "The alignment of {name} must be a power of two, not {minimum_alignment}"
+            )));
+        }
+
+        let endianness = match cls.getattr("_ENDIANNESS") {
+            Ok(value) if !value.is_none() => Some(value.extract::<Endianness>()?),
+            _ => None,
+        };
+
+        let bit_order = match cls.getattr("_BIT_ORDER") {
+            Ok(value) if !value.is_none() => value.extract::<BitOrder>()?,
+            _ => BitOrder::default(),
+        };
+
+        let declared = cls
+            .getattr("_CFIELDS")
+            .ok()
+            .and_then(|fields| fields.downcast::<PyDict>().ok())
+            .ok_or_else(|| {
+                LAYOUT_ERROR.new_err(format!(
+                    "{name} must declare its fields as a dict named _CFIELDS"
+                ))
+            })?;
+        let mut fields = Vec::with_capacity(declared.len());
+        let mut end = 0_usize;
+        let mut alignment = minimum_alignment;
+        let mut allocator = BitAllocator::new(bit_order);
+
+        for (key, value) in declared {
+            let field_name = key.extract::<String>()?;
+            let declared = Field::declared(&field_name, cls, value)?;
+            let type_ = declared.type_.as_ref(cls.py());
+            // A nested struct keeps the byte order it was declared with unless the field says otherwise
+            let field_endianness = match (declared.endianness, type_.is_subclass_of::<CStruct>()?) {
+                (Some(endianness), _) => Some(endianness),
+                (None, true) => Self::of(type_)?.get().endianness,
+                (None, false) => None,
+            };
+            let count = match Array::count_of(type_)? {
+                Some(count) => {
+                    // The elements have to be of one size for the length to determine the size of the array
+                    Array::element_size(type_.getattr("__origin__")?.downcast::<PyType>()?)?;
+                    Some(Self::integer_field(
+                        cls,
+                        &fields,
+                        &field_name,
+                        &count,
+                        "length",
+                    )?)
+                }
+                None => None,
+            };
+            let tag = match Tagged::tag_of(type_)? {
+                Some(tag) => Some(Self::integer_field(cls, &fields, &field_name, &tag, "tag")?),
+                None => None,
+            };
+            // Dynamically sized fields take up as little space as their empty value does (e.g. the terminator of a `cstring`)
+            let field_dynamic = is_dynamic(type_)?;
+            if let Some(explicit) = declared.offset {
+                if union {
+                    return Err(LAYOUT_ERROR.new_err(format!(
+                        "The member {field_name} of {name} can't have an offset since every member of a union starts at 0"
+                    )));
+                }
+                if let Some(dynamic) = fields.iter().find(|field| field.dynamic) {
+                    return Err(LAYOUT_ERROR.new_err(format!(
+                        "The field {field_name} of {name} can't have an offset since it follows the dynamically sized field {}",
+                        dynamic.name
+                    )));
+                }
+                if let Some(overlapped) = fields
+                    .iter()
+                    .find(|field| field.offset + field.size > explicit)
+                {
+                    return Err(LAYOUT_ERROR.new_err(format!(
+                        "The field {field_name} of {name} at offset {explicit:#x} overlaps the field {} at offset {:#x} which is {} bytes large",
+                        overlapped.name, overlapped.offset, overlapped.size
+                    )));
+                }
+            }
+            if let Some(constant) = &declared.constant {
+                if field_dynamic || declared.bits.is_some() || type_.is_subclass_of::<Pad>()? {
+                    return Err(LAYOUT_ERROR.new_err(format!(
+                        "The field {field_name} of {name} can't be a constant since it is a bitfield, padding or dynamically sized"
+                    )));
+                }
+                // Encoding the value up front makes values which the field can't hold a layout error rather than a decoding one
+                let constant_endianness = field_endianness.or(endianness).unwrap_or_default();
+                if let Err(err) = encode(
+                    type_,
+                    constant.as_ref(cls.py()),
+                    constant_endianness,
+                    declared.overflow,
+                ) {
+                    return Err(LAYOUT_ERROR.new_err(format!(
+                        "The constant of the field {field_name} of {name} can't be stored in a {}: {err}",
+                        type_.name()?
+                    )));
+                }
+            }
+            if union && field_dynamic {
+                return Err(LAYOUT_ERROR.new_err(format!(
+                    "The member {field_name} of {name} must have a fixed size since it overlaps the other members, got {}",
+                    type_.name()?
+                )));
+            }
+            let size = match (count.or(tag), field_dynamic) {
+                (Some(_), _) => 0,
+                (None, true) => type_
+                    .call_method1("__new__", (type_,))?
+                    .call_method0("__bytes__")?
+                    .len()?,
+                (None, false) => type_.call_method0("__packed_size__")?.extract::<usize>()?,
+            };
+            let field_alignment = match packed {
+                true => 1,
+                false => type_.call_method0("__alignment__")?.extract::<usize>()?,
+            };
+            // Members of a union never share a storage unit since each of them starts at the beginning of the union,
+            // and neither does a bitfield placed at an offset of its own
+            if union || declared.offset.is_some() {
+                allocator.close();
+            }
+            let bits = match declared.bits {
+                Some(width) => {
+                    Some(allocator.allocate(cls, &field_name, type_, width, field_endianness)?)
+                }
+                None => {
+                    allocator.close();
+                    None
+                }
+            };
+
+            // A bitfield which fits into the storage unit of the bitfield before it doesn't take up any more space
+            if let (Some(bits @ Bits { shared: true, .. }), Some(previous)) = (bits, fields.last())
+            {
+                let offset = previous.offset;
+                fields.push(FieldLayout {
+                    name: field_name,
+                    type_: type_.into(),
+                    offset,
+                    size,
+                    alignment: field_alignment,
+                    dynamic: false,
+                    count: None,
+                    tag: None,
+                    bits: Some(bits),
+                    overflow: declared.overflow,
+                    endianness: field_endianness,
+                    explicit_offset: false,
+                    hidden: false,
+                    constant: None,
+                });
+                continue;
+            }
+
+            // An explicit offset is taken as is, the bytes between it and the field before it become padding
+            let offset = match (union, declared.offset) {
+                (true, _) => 0,
+                (false, Some(explicit)) => explicit,
+                (false, None) => align_to(end, field_alignment),
+            };
+            fields.push(FieldLayout {
+                name: field_name,
+                type_: type_.into(),
+                offset,
+                size,
+                alignment: field_alignment,
+                dynamic: field_dynamic,
+                count,
+                tag,
+                bits,
+                overflow: declared.overflow,
+                endianness: field_endianness,
+                explicit_offset: declared.offset.is_some(),
+                hidden: type_.is_subclass_of::<Pad>()?,
+                constant: declared.constant,
+            });
+            end = end.max(offset + size);
+            alignment = alignment.max(field_alignment);
+        }
+
+        let index = fields
+            .iter()
+            .enumerate()
+            .map(|(position, field)| (field.name.clone(), position))
+            .collect();
+
+        let mut constants = false;
+        for field in &fields {
+            let type_ = field.type_.as_ref(cls.py());
+            constants |= field.constant.is_some()
+                || (type_.is_subclass_of::<CStruct>()? && Self::of(type_)?.get().constants);
+        }
+
+        Ok(Self {
+            dynamic: fields.iter().any(|field| field.dynamic),
+            placements: fields
+                .iter()
+                .map(|field| (field.offset, field.size))
+                .collect(),
+            constants,
+            fields,
+            index,
+            // The tail padding makes sure that consecutive instances in an array stay aligned
+            size: align_to(end, alignment),
+            alignment,
+            endianness,
+        })
+    }
+
+    /// Returns the position of the field `referenced` which holds the `role` (e.g. the length) of the field `name`.
+    /// It has to be an integer field declared before it.
+    fn integer_field(
+        cls: &PyType,
+        fields: &[FieldLayout],
+        name: &str,
+        referenced: &str,
+        role: &str,
+    ) -> PyResult<usize> {
+        let py = cls.py();
+        let struct_name = cls.name()?;
+        let position = fields
+            .iter()
+            .position(|field| field.name == referenced)
+            .ok_or_else(|| {
+                LAYOUT_ERROR.new_err(format!(
+                    "The {role} of the field {name} of {struct_name} must be a field declared before it, got {referenced}"
+                ))
+            })?;
+
+        if fields[position].bits.is_some() {
+            return Err(LAYOUT_ERROR.new_err(format!(
+                "The {role} of the field {name} of {struct_name} can't be the bitfield {referenced}"
+            )));
+        }
+        let referenced_type = fields[position].type_.as_ref(py);
+        if fields[position].dynamic || !referenced_type.hasattr("__index__")? {
+            return Err(LAYOUT_ERROR.new_err(format!(
+                "The {role} of the field {name} of {struct_name} must be an integer, but {referenced} is a {}",
+                referenced_type.name()?
+            )));
+        }
+        Ok(position)
+    }
+
+    /// Reads the integer field at `position`, which is located at `placement` within `buffer`
+    fn integer<'py>(
+        &self,
+        py: Python<'py>,
+        position: usize,
+        placement: (usize, usize),
+        buffer: &[u8],
+        endianness: Endianness,
+    ) -> PyResult<&'py PyAny> {
+        let field = &self.fields[position];
+        let (offset, size) = placement;
+        let Some(packed) = buffer.get(offset..offset + size) else {
+            return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                "The field {} requires a buffer of at least {} bytes, got {}",
+                field.name,
+                offset + size,
+                buffer.len()
+            )));
+        };
+
+        field
+            .type_
+            .clone_ref(py)
+            .into_ref(py)
+            .call1((
+                PyBytes::new(py, packed),
+                field.endianness.unwrap_or(endianness),
+            ))?
+            .call_method0("__index__")
+    }
+
+    /// Reads the length of a counted array from the field at `count`, which is located at `placement` within `buffer`
+    pub fn count(
+        &self,
+        py: Python<'_>,
+        count: usize,
+        placement: (usize, usize),
+        buffer: &[u8],
+        endianness: Endianness,
+    ) -> PyResult<usize> {
+        let length = self.integer(py, count, placement, buffer, endianness)?;
+        match length.extract::<usize>() {
+            Ok(length) => Ok(length),
+            Err(_) => Err(BUFFER_SIZE_ERROR.new_err(format!(
+                "The field {} can't be the length of an array since it is {length}",
+                self.fields[count].name
+            ))),
+        }
+    }
+
+    /// Returns the variant of the tagged union `type_` selected by the field at `tag`, which is located at `placement` within `buffer`.
+    /// `None` means that the tag is unknown and the payload is read as raw bytes.
+    pub fn variant<'py>(
+        &self,
+        type_: &'py PyType,
+        tag: usize,
+        placement: (usize, usize),
+        buffer: &[u8],
+        endianness: Endianness,
+    ) -> PyResult<Option<&'py PyType>> {
+        Tagged::variant(
+            type_,
+            self.integer(type_.py(), tag, placement, buffer, endianness)?,
+        )
+    }
+
+    /// Returns the compiled layout of `cls`, compiling and storing it on the class the first time it is requested
+    pub fn of(cls: &PyType) -> PyResult<&PyCell<Self>> {
+        // Only look at the class itself since a subclass may declare different fields than its parent
+        let cached = cls.getattr("__dict__")?.call_method1("get", ("_LAYOUT",))?;
+
+        if let Ok(layout) = cached.downcast::<PyCell<Self>>() {
+            return Ok(layout);
+        }
+
+        let layout = PyCell::new(cls.py(), Self::compile(cls)?)?;
+        cls.setattr("_LAYOUT", layout)?;
+        Ok(layout)
+    }
+
+    pub fn fields(&self) -> &[FieldLayout] {
+        &self.fields
+    }
+
+    pub fn endianness(&self) -> Option<Endianness> {
+        self.endianness
+    }
+
+    pub fn constants(&self) -> bool {
+        self.constants
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldLayout> {
+        self.index.get(name).map(|&position| &self.fields[position])
+    }
+
+    /// The position of the field named `name` in declaration order
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.index.get(name).copied()
+    }
+
+    /// Locates every field of the instance of `cls` stored in `buffer` in the given byte order.
+    /// The fields are placed the same way as when compiling the layout, except that dynamically sized fields are measured.
+    pub fn placements(
+        &self,
+        cls: &PyType,
+        buffer: &[u8],
+        endianness: Endianness,
+    ) -> PyResult<Placements<'_>> {
+        if !self.dynamic {
+            return Ok(Placements {
+                fields: Cow::Borrowed(&self.placements),
+                size: self.size,
+            });
+        }
+
+        let py = cls.py();
+        let mut fields = Vec::with_capacity(self.fields.len());
+        let mut offset = 0_usize;
+        for field in &self.fields {
+            if let (Some(Bits { shared: true, .. }), Some(&previous)) = (field.bits, fields.last())
+            {
+                fields.push(previous);
+                continue;
+            }
+            // Only fields before the first dynamically sized one can have an explicit offset, so it is the same in every instance
+            offset = match field.explicit_offset {
+                true => field.offset,
+                false => align_to(offset, field.alignment),
+            };
+            let remaining = buffer.get(offset..).unwrap_or_default();
+            let type_ = field.type_.as_ref(py);
+            let field_endianness = field.endianness.unwrap_or(endianness);
+            let size = match (field.count, field.tag, field.dynamic) {
+                (Some(count), _, _) => {
+                    let length = self.count(py, count, fields[count], buffer, endianness)?;
+                    let element_type = type_.getattr("__origin__")?.downcast::<PyType>()?;
+                    // The count comes from the buffer, so it can claim more elements than the buffer (or memory) holds
+                    match length.checked_mul(Array::element_size(element_type)?) {
+                        Some(size) if size <= remaining.len() => size,
+                        _ => {
+                            return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                                "The field {} of {} has a count of {length}, which requires more than the {} bytes left in the buffer",
+                                field.name,
+                                cls.name()?,
+                                remaining.len()
+                            )))
+                        }
+                    }
+                }
+                // A payload with an unknown tag takes up the rest of the buffer
+                (_, Some(tag), _) => {
+                    match self.variant(type_, tag, fields[tag], buffer, endianness)? {
+                        Some(variant) => measure(variant, remaining, field_endianness)?,
+                        None => remaining.len(),
+                    }
+                }
+                (None, None, true) => measure(type_, remaining, field_endianness)?,
+                (None, None, false) => field.size,
+            };
+            fields.push((offset, size));
+            offset += size;
+        }
+
+        let size = align_to(offset, self.alignment);
+        if size > buffer.len() {
+            return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                "{} requires a buffer of at least {size} bytes, got {}",
+                cls.name()?,
+                buffer.len()
+            )));
+        }
+        Ok(Placements {
+            fields: Cow::Owned(fields),
+            size,
+        })
+    }
+
+    /// Packs the bytes of every field (in declaration order) into an instance, inserting the padding its alignment requires.
+    /// Fields with an explicit offset are placed at it, the bytes before them are zeroed like any other padding
+    pub fn pack(&self, values: &[Vec<u8>]) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        for (field, value) in self.fields.iter().zip(values) {
+            // Bitfields which share a storage unit are packed once, as part of the first of them
+            if field.bits.is_some_and(|bits| bits.shared) {
+                continue;
+            }
+            let offset = match field.explicit_offset {
+                true => field.offset,
+                false => align_to(buffer.len(), field.alignment),
+            };
+            buffer.resize(offset, 0);
+            buffer.extend_from_slice(value);
+        }
+        buffer.resize(align_to(buffer.len(), self.alignment), 0);
+        buffer
+    }
+}
+
+#[pymethods]
+impl Layout {
+    #[getter]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    #[getter]
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    /// Whether the size of instances depends on their contents, in which case `size` and the offsets are those of an empty instance
+    #[getter]
+    pub fn dynamic(&self) -> bool {
+        self.dynamic
+    }
+
+    /// The name, type, offset and size of every field in declaration order
+    #[getter(fields)]
+    fn py_fields(&self, py: Python<'_>) -> Vec<(String, Py<PyType>, usize, usize)> {
+        self.fields
+            .iter()
+            .map(|field| {
+                (
+                    field.name.clone(),
+                    field.type_.clone_ref(py),
+                    field.offset,
+                    field.size,
+                )
+            })
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.fields.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Layout(size={}, alignment={}, fields=[{}])",
+            self.size,
+            self.alignment,
+            self.fields
+                .iter()
+                .map(|field| format!("{}@{}:{}", field.name, field.offset, field.size))
+                .format(", ")
+        )
+    }
+}
@@ -1,12 +1,42 @@
 mod abstractions;
+mod array;
+mod bitfield;
+mod cenum;
+mod cflags;
 mod cstruct;
+mod cunion;
+mod encoding;
+mod field;
+mod layout;
+mod numpy;
+mod padding;
+mod prefixed;
 mod primitives;
+mod records;
 mod sentinels;
+mod storage;
+mod strings;
+mod tagged;
 
 pub use abstractions::PyShaped;
+pub use array::Array;
+pub use bitfield::{BitAllocator, Bits};
+pub use cenum::CEnum;
+pub use cflags::CFlags;
 pub use cstruct::CStruct;
+pub use cunion::CUnion;
+pub use encoding::encode;
+pub use field::Field;
+pub use layout::{declared_endianness, is_dynamic, measure, Layout};
+pub use numpy::{dtype_of, scalar_dtype};
+pub use padding::{Pad, Reserved};
+pub use prefixed::{PascalString, PrefixedBytes};
 pub use primitives::{
     Bool, Float16, Float32, Float64, Int128, Int16, Int32, Int64, Int8, Uint128, Uint16, Uint32,
     Uint64, Uint8,
 };
-pub use sentinels::{BigEndian, Endianness, LittleEndian, NativeEndian};
+pub use records::{RecordIterator, Records};
+pub use sentinels::{BitOrder, Endianness, Overflow, UnknownValue};
+pub use storage::{bytes_of, with_bytes, View};
+pub use strings::{plain_value, CStr, CString, Encoding, Termination};
+pub use tagged::Tagged;
@@ -0,0 +1,58 @@
+use super::{Array, Endianness, PyShaped};
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyTuple, PyType};
+
+/// Creates the NumPy dtype of a scalar, where `kind` is the NumPy type code (e.g. `i` for signed integers)
+pub fn scalar_dtype(
+    cls: &PyType,
+    kind: char,
+    size: usize,
+    endianness: Endianness,
+) -> PyResult<&PyAny> {
+    if size > 8 {
+        return Err(PyTypeError::new_err(format!(
+            "NumPy has no equivalent of {}",
+            cls.name()?
+        )));
+    }
+
+    let byte_order = match (size, endianness) {
+        (1, _) => '|',
+        (_, Endianness::Native) => '=',
+        (_, Endianness::Little) => '<',
+        (_, Endianness::Big) => '>',
+    };
+    cls.py()
+        .import("numpy")?
+        .getattr("dtype")?
+        .call1((format!("{byte_order}{kind}{size}"),))
+}
+
+/// Returns the NumPy dtype of any shaped type
+pub fn dtype_of(cls: &PyType, endianness: Endianness) -> PyResult<&PyAny> {
+    cls.call_method1("dtype", (endianness,))
+}
+
+/// Creates a NumPy array of the value which shares its memory rather than copying it.
+/// Scalars and structs become zero-dimensional arrays, arrays keep their shape.
+pub fn as_array(value: &PyCell<PyShaped>) -> PyResult<&PyAny> {
+    let py = value.py();
+    let cls = value.get_type();
+    let endianness = value.borrow().endianness;
+    let numpy = py.import("numpy")?;
+
+    let (dtype, shape) = match cls.is_subclass_of::<Array>()? {
+        true => {
+            let (element_type, shape) = Array::dimensions(cls)?;
+            (dtype_of(element_type, endianness)?, PyTuple::new(py, shape))
+        }
+        false => (dtype_of(cls, endianness)?, PyTuple::empty(py)),
+    };
+    // Elements which are arrays themselves have a sub-array dtype, whose dimensions NumPy appends to the array's
+    let shape = shape.call_method1("__add__", (dtype.getattr("shape")?,))?;
+
+    numpy
+        .call_method1("frombuffer", (value, dtype))?
+        .call_method1("reshape", (shape,))
+}
@@ -0,0 +1,149 @@
+use super::{bytes_of, Endianness, PyShaped};
+use crate::errors::{BUFFER_SIZE_ERROR, LAYOUT_ERROR};
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::pyclass_init::PyClassInitializer;
+use pyo3::types::{PyBytes, PyTuple, PyType};
+
+/// Bytes which only take up space in a struct, e.g. `pad[3]` for the filler before an aligned field.
+/// Padding fields aren't part of `__init__` or `__repr__` and can't be read or assigned,
+/// but their bytes are kept as they are so that packing a decoded struct gives back the original buffer.
+#[pyclass(module = "_cerialize", name = "pad", subclass, weakref, extends=PyShaped)]
+pub struct Pad();
+
+impl Pad {
+    fn length(cls: &PyType) -> PyResult<usize> {
+        match cls.getattr("_LENGTH") {
+            Ok(length) if !length.is_none() => length.extract::<usize>(),
+            _ => Err(LAYOUT_ERROR.new_err(format!(
+                "{name} doesn't have a length, create it by subscripting it, e.g. {name}[4]",
+                name = cls.name()?
+            ))),
+        }
+    }
+
+    /// Creates zeroed padding, or padding holding the bytes in `value`
+    pub(crate) fn new(
+        cls: &PyType,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let length = Self::length(cls)?;
+        let buffer = match value {
+            Some(value) => match bytes_of(value)? {
+                Some(buffer) if buffer.len() == length => buffer,
+                Some(buffer) => {
+                    return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                        "{} requires a buffer of exactly {length} bytes, got {}",
+                        cls.name()?,
+                        buffer.len()
+                    )))
+                }
+                None => {
+                    return Err(PyTypeError::new_err(format!(
+                        "{} can only be created from a bytes-like object, got {}",
+                        cls.name()?,
+                        value.get_type().name()?
+                    )))
+                }
+            },
+            None => vec![0; length],
+        };
+
+        Ok((
+            Self(),
+            PyShaped::with_buffer(cls.py(), buffer, endianness.unwrap_or_default())?,
+        ))
+    }
+}
+
+#[pymethods]
+impl Pad {
+    #[new]
+    #[classmethod]
+    #[pyo3(signature = (value = None, endianness = None))]
+    fn py_new(
+        cls: &PyType,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+    ) -> PyResult<(Self, PyShaped)> {
+        Self::new(cls, value, endianness)
+    }
+
+    /// `pad[length]` creates the padding of `length` bytes
+    #[pyo3(signature = (*args))]
+    #[classmethod]
+    fn __class_getitem__(cls: &PyType, args: &PyTuple) -> PyResult<Py<PyType>> {
+        let py = cls.py();
+        if Self::length(cls).is_ok() {
+            return PyShaped::__class_getitem__(cls, py, args);
+        }
+
+        let arg = args.get_item(0)?;
+        let Some(length) = arg.extract::<usize>().ok().filter(|&length| length > 0) else {
+            return Err(LAYOUT_ERROR.new_err(format!(
+                "The length of {} must be a positive integer, got {arg}",
+                cls.name()?
+            )));
+        };
+
+        PyShaped::derived_type(cls, format!("{}[{length}]", cls.name()?), |class_name| {
+            let new_type = py
+                .import("types")?
+                .getattr("new_class")?
+                .call1((class_name, (cls,)))?
+                .downcast::<PyType>()?;
+            new_type.setattr("__module__", cls.getattr("__module__")?)?;
+            new_type.setattr("_LENGTH", length)?;
+            Ok(new_type.into())
+        })
+    }
+
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
+        let py = slf.py();
+        let bytes = PyBytes::new(py, &slf.borrow().as_ref().buffer.to_vec(py));
+        Ok(format!("{}({})", slf.get_type().name()?, bytes.repr()?))
+    }
+
+    #[classmethod]
+    fn __packed_size__(cls: &PyType) -> PyResult<usize> {
+        Self::length(cls)
+    }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(1)
+    }
+
+    /// Returns the equivalent NumPy dtype, raw bytes of the same length
+    #[classmethod]
+    #[pyo3(signature = (_endianness = None))]
+    fn dtype(cls: &PyType, _endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        cls.py()
+            .import("numpy")?
+            .getattr("dtype")?
+            .call1((format!("V{}", Self::length(cls)?),))
+    }
+}
+
+/// Bytes which the format sets aside for future use, e.g. `reserved[8]`.
+/// They are laid out and hidden the same way as `pad`, the name only documents why the bytes are there.
+#[pyclass(module = "_cerialize", name = "reserved", subclass, extends=Pad)]
+pub struct Reserved();
+
+#[pymethods]
+impl Reserved {
+    #[new]
+    #[classmethod]
+    #[pyo3(signature = (value = None, endianness = None))]
+    fn new(
+        cls: &PyType,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+    ) -> PyResult<PyClassInitializer<Self>> {
+        let (pad, shaped) = Pad::new(cls, value, endianness)?;
+        Ok(PyClassInitializer::from(shaped)
+            .add_subclass(pad)
+            .add_subclass(Self()))
+    }
+}
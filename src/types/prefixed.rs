@@ -0,0 +1,400 @@
+use super::{bytes_of, encode, Encoding, Endianness, PyShaped};
+use crate::errors::{BUFFER_SIZE_ERROR, LAYOUT_ERROR};
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString, PyTuple, PyType};
+
+/// Returns the integer type which holds the length of a length-prefixed type
+fn prefix_of(cls: &PyType) -> PyResult<&PyType> {
+    match cls.getattr("_PREFIX") {
+        Ok(prefix) if !prefix.is_none() => Ok(prefix.downcast::<PyType>()?),
+        _ => Err(LAYOUT_ERROR.new_err(format!(
+            "{name} doesn't have a length prefix, create it by subscripting it with an integer type, e.g. {name}[u32]",
+            name = cls.name()?
+        ))),
+    }
+}
+
+/// Returns the size of the prefix and the length of the payload which follows it at the start of `buffer`
+fn read_length(cls: &PyType, buffer: &[u8], endianness: Endianness) -> PyResult<(usize, usize)> {
+    let py = cls.py();
+    let prefix = prefix_of(cls)?;
+    let prefix_size = prefix.call_method0("__packed_size__")?.extract::<usize>()?;
+    let Some(packed) = buffer.get(..prefix_size) else {
+        return Err(BUFFER_SIZE_ERROR.new_err(format!(
+            "{} requires a buffer of at least {prefix_size} bytes for its length, got {}",
+            cls.name()?,
+            buffer.len()
+        )));
+    };
+
+    let length = prefix
+        .call1((PyBytes::new(py, packed), endianness))?
+        .call_method0("__index__")?;
+    match length.extract::<usize>() {
+        Ok(length) => Ok((prefix_size, length)),
+        Err(_) => {
+            Err(BUFFER_SIZE_ERROR
+                .new_err(format!("{} can't have a length of {length}", cls.name()?)))
+        }
+    }
+}
+
+/// Returns the size of the prefix and of the whole value at the start of `buffer`, which has to hold all of it
+fn extent(cls: &PyType, buffer: &[u8], endianness: Endianness) -> PyResult<(usize, usize)> {
+    let (prefix_size, length) = read_length(cls, buffer, endianness)?;
+    // The length comes from the buffer, so it can claim more bytes than any buffer holds
+    match prefix_size.checked_add(length) {
+        Some(size) if size <= buffer.len() => Ok((prefix_size, size)),
+        _ => Err(BUFFER_SIZE_ERROR.new_err(format!(
+            "{} has a length of {length}, which requires more than the {} bytes after its prefix",
+            cls.name()?,
+            buffer.len() - prefix_size
+        ))),
+    }
+}
+
+/// Returns the size of the value at the start of `buffer`, including its prefix
+fn measure(cls: &PyType, buffer: &[u8], endianness: Endianness) -> PyResult<usize> {
+    Ok(extent(cls, buffer, endianness)?.1)
+}
+
+/// Returns the payload of the packed value in `buffer`
+fn payload(cls: &PyType, buffer: &[u8], endianness: Endianness) -> PyResult<Vec<u8>> {
+    let (prefix_size, size) = extent(cls, buffer, endianness)?;
+    Ok(buffer[prefix_size..size].to_vec())
+}
+
+/// Packs `payload` behind its length, which raises a `ValueRangeError` if the prefix can't hold it
+fn pack(cls: &PyType, payload: &[u8], endianness: Endianness) -> PyResult<Vec<u8>> {
+    let py = cls.py();
+    let mut buffer = encode(
+        prefix_of(cls)?,
+        payload.len().into_py(py).as_ref(py),
+        endianness,
+        None,
+    )?;
+    buffer.extend_from_slice(payload);
+    Ok(buffer)
+}
+
+/// Creates the type of `cls` with the given prefix, e.g. `bytes_prefixed[u32]`
+fn subscript(cls: &PyType, prefix: &PyAny, encoding: Option<Encoding>) -> PyResult<Py<PyType>> {
+    let py = cls.py();
+    let name = cls.name()?;
+    let prefix = prefix
+        .downcast::<PyType>()
+        .ok()
+        .filter(|prefix| {
+            prefix.is_subclass_of::<PyShaped>().unwrap_or(false)
+                && prefix.hasattr("__index__").unwrap_or(false)
+        })
+        .ok_or_else(|| {
+            LAYOUT_ERROR.new_err(format!(
+                "The length of {name} must be prefixed by an integer type, got {prefix}"
+            ))
+        })?;
+
+    let mut parameters = vec![prefix.name()?.to_string()];
+    if let Some(encoding) = encoding.filter(|&encoding| encoding != Encoding::default()) {
+        parameters.push(encoding.name().to_string());
+    }
+    let class_name = format!("{name}[{}]", parameters.join(","));
+
+    PyShaped::derived_type(cls, class_name, |class_name| {
+        let new_type = py
+            .import("types")?
+            .getattr("new_class")?
+            .call1((class_name, (cls,)))?
+            .downcast::<PyType>()?;
+        new_type.setattr("__module__", cls.getattr("__module__")?)?;
+        new_type.setattr("_PREFIX", prefix)?;
+        if let Some(encoding) = encoding {
+            new_type.setattr("_ENCODING", encoding.into_py(py))?;
+        }
+        Ok(new_type.into())
+    })
+}
+
+fn dynamic_size_error(cls: &PyType) -> PyResult<PyErr> {
+    Ok(LAYOUT_ERROR.new_err(format!(
+        "{} is dynamically sized, the size of a value depends on its length prefix",
+        cls.name()?
+    )))
+}
+
+/// Returns the contents of `slf`, decoded with the byte order it is stored in
+fn contents(slf: &PyAny) -> PyResult<Vec<u8>> {
+    let shaped = slf.downcast::<PyCell<PyShaped>>()?.borrow();
+    shaped.buffer.read(slf.py(), |buffer| {
+        payload(slf.get_type(), buffer, shaped.endianness)
+    })
+}
+
+/// Bytes which are preceded by their length, e.g. `bytes_prefixed[u32]` is a `u32` followed by that many bytes.
+/// The prefix is stored in the byte order of the struct (or field) and is derived from the payload whenever the value is assigned.
+#[pyclass(module = "_cerialize", name = "bytes_prefixed", subclass, weakref, extends=PyShaped)]
+pub struct PrefixedBytes();
+
+impl PrefixedBytes {
+    pub fn measure(cls: &PyType, buffer: &[u8], endianness: Endianness) -> PyResult<usize> {
+        measure(cls, buffer, endianness)
+    }
+
+    /// Returns the payload as `bytes`, which is how fields of this type are read
+    pub fn payload_of<'py>(
+        cls: &PyType,
+        py: Python<'py>,
+        buffer: &[u8],
+        endianness: Endianness,
+    ) -> PyResult<&'py PyBytes> {
+        Ok(PyBytes::new(py, &payload(cls, buffer, endianness)?))
+    }
+}
+
+#[pymethods]
+impl PrefixedBytes {
+    /// Creates an empty value, or one holding the bytes-like `value`
+    #[new]
+    #[classmethod]
+    #[pyo3(signature = (value = None, endianness = None))]
+    fn new(
+        cls: &PyType,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or_default();
+        let payload = match value {
+            None => Vec::new(),
+            Some(value) if value.downcast::<PyCell<Self>>().is_ok() => contents(value)?,
+            Some(value) => match bytes_of(value)? {
+                Some(payload) => payload,
+                None => {
+                    return Err(PyTypeError::new_err(format!(
+                        "{} can only be created from a bytes-like object, got {}",
+                        cls.name()?,
+                        value.get_type().name()?
+                    )))
+                }
+            },
+        };
+
+        Ok((
+            Self(),
+            PyShaped::with_buffer(cls.py(), pack(cls, &payload, endianness)?, endianness)?,
+        ))
+    }
+
+    /// `bytes_prefixed[prefix]` creates the type whose length is stored as a `prefix`
+    #[classmethod]
+    fn __class_getitem__(cls: &PyType, prefix: &PyAny) -> PyResult<Py<PyType>> {
+        subscript(cls, prefix, None)
+    }
+
+    /// The bytes after the length prefix
+    #[getter]
+    fn data(slf: &PyCell<Self>) -> PyResult<&PyBytes> {
+        Ok(PyBytes::new(slf.py(), &contents(slf)?))
+    }
+
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
+        Ok(format!(
+            "{}({})",
+            slf.get_type().name()?,
+            Self::data(slf)?.repr()?
+        ))
+    }
+
+    /// Values compare by their payload, both with each other and with `bytes`
+    fn __richcmp__(slf: &PyCell<Self>, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        let py = slf.py();
+        let other = match other.downcast::<PyCell<Self>>() {
+            Ok(other) => Self::data(other)?,
+            Err(_) => match other.downcast::<PyBytes>() {
+                Ok(other) => other,
+                Err(_) => return Ok(py.NotImplemented()),
+            },
+        };
+        Ok(Self::data(slf)?.rich_compare(other, op)?.into())
+    }
+
+    fn __hash__(slf: &PyCell<Self>) -> PyResult<isize> {
+        Self::data(slf)?.hash()
+    }
+
+    fn __len__(slf: &PyCell<Self>) -> PyResult<usize> {
+        Ok(contents(slf)?.len())
+    }
+
+    #[classmethod]
+    fn __packed_size__(cls: &PyType) -> PyResult<usize> {
+        Err(dynamic_size_error(cls)?)
+    }
+
+    #[classmethod]
+    fn __alignment__(cls: &PyType) -> PyResult<usize> {
+        prefix_of(cls)?
+            .call_method0("__alignment__")?
+            .extract::<usize>()
+    }
+
+    #[classmethod]
+    #[pyo3(signature = (_endianness = None))]
+    fn dtype(cls: &PyType, _endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        Err(PyTypeError::new_err(format!(
+            "NumPy has no equivalent of {}",
+            cls.name()?
+        )))
+    }
+}
+
+/// Text which is preceded by its length in bytes, e.g. `pascal_string[u8]` or `pascal_string[u16, "utf-8"]`.
+/// The text is encoded as ASCII unless another encoding follows the prefix.
+#[pyclass(module = "_cerialize", name = "pascal_string", subclass, weakref, extends=PyShaped)]
+pub struct PascalString();
+
+impl PascalString {
+    fn encoding(cls: &PyType) -> PyResult<Encoding> {
+        match cls.getattr("_ENCODING") {
+            Ok(encoding) => encoding.extract::<Encoding>(),
+            Err(_) => Ok(Encoding::default()),
+        }
+    }
+
+    pub fn measure(cls: &PyType, buffer: &[u8], endianness: Endianness) -> PyResult<usize> {
+        measure(cls, buffer, endianness)
+    }
+
+    pub fn text<'py>(
+        cls: &PyType,
+        py: Python<'py>,
+        buffer: &[u8],
+        endianness: Endianness,
+    ) -> PyResult<&'py PyString> {
+        Self::encoding(cls)?.decode(py, &payload(cls, buffer, endianness)?)
+    }
+
+    fn value(slf: &PyCell<Self>) -> PyResult<&PyString> {
+        Self::encoding(slf.get_type())?.decode(slf.py(), &contents(slf)?)
+    }
+}
+
+#[pymethods]
+impl PascalString {
+    /// Creates an empty string, or one holding `value` which is either a `str` or the packed bytes of the string (including its prefix)
+    #[new]
+    #[classmethod]
+    #[pyo3(signature = (value = None, endianness = None))]
+    fn new(
+        cls: &PyType,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or_default();
+        let buffer = match value {
+            None => pack(cls, &[], endianness)?,
+            Some(value) => {
+                if let Ok(text) = value.downcast::<PyString>() {
+                    pack(cls, &Self::encoding(cls)?.encode(text)?, endianness)?
+                } else if let Some(buffer) = bytes_of(value)? {
+                    if measure(cls, &buffer, endianness)? != buffer.len() {
+                        return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                            "{} requires a buffer which ends with its text, got {} bytes",
+                            cls.name()?,
+                            buffer.len()
+                        )));
+                    }
+                    buffer
+                } else {
+                    return Err(PyTypeError::new_err(format!(
+                        "{} can't be created from a value of type {}",
+                        cls.name()?,
+                        value.get_type().name()?
+                    )));
+                }
+            }
+        };
+
+        Ok((Self(), PyShaped::with_buffer(cls.py(), buffer, endianness)?))
+    }
+
+    /// `pascal_string[prefix, encoding]` creates the string type whose length is stored as a `prefix`
+    #[pyo3(signature = (*args))]
+    #[classmethod]
+    fn __class_getitem__(cls: &PyType, args: &PyTuple) -> PyResult<Py<PyType>> {
+        let arg = args.get_item(0)?;
+        let (prefix, encoding) = match arg.downcast::<PyTuple>() {
+            Ok(options) if options.len() == 2 => {
+                let encoding = options.get_item(1)?;
+                let encoding = match encoding.downcast::<PyString>() {
+                    Ok(name) => Encoding::from_name(name)?,
+                    Err(_) => encoding.extract::<Encoding>()?,
+                };
+                (options.get_item(0)?, Some(encoding))
+            }
+            Ok(options) => {
+                return Err(LAYOUT_ERROR.new_err(format!(
+                    "{} takes a length prefix and optionally an encoding, got {options}",
+                    cls.name()?
+                )))
+            }
+            Err(_) => (arg, None),
+        };
+        subscript(cls, prefix, encoding)
+    }
+
+    fn __str__(slf: &PyCell<Self>) -> PyResult<&PyString> {
+        Self::value(slf)
+    }
+
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
+        Ok(format!(
+            "{}({})",
+            slf.get_type().name()?,
+            Self::value(slf)?.repr()?
+        ))
+    }
+
+    /// Strings compare by their text, both with each other and with `str`
+    fn __richcmp__(slf: &PyCell<Self>, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        let py = slf.py();
+        let other = match other.downcast::<PyCell<Self>>() {
+            Ok(other) => Self::value(other)?,
+            Err(_) => match other.downcast::<PyString>() {
+                Ok(other) => other,
+                Err(_) => return Ok(py.NotImplemented()),
+            },
+        };
+        Ok(Self::value(slf)?.rich_compare(other, op)?.into())
+    }
+
+    fn __hash__(slf: &PyCell<Self>) -> PyResult<isize> {
+        Self::value(slf)?.hash()
+    }
+
+    fn __len__(slf: &PyCell<Self>) -> PyResult<usize> {
+        Self::value(slf)?.len()
+    }
+
+    #[classmethod]
+    fn __packed_size__(cls: &PyType) -> PyResult<usize> {
+        Err(dynamic_size_error(cls)?)
+    }
+
+    #[classmethod]
+    fn __alignment__(cls: &PyType) -> PyResult<usize> {
+        prefix_of(cls)?
+            .call_method0("__alignment__")?
+            .extract::<usize>()
+    }
+
+    #[classmethod]
+    #[pyo3(signature = (_endianness = None))]
+    fn dtype(cls: &PyType, _endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        Err(PyTypeError::new_err(format!(
+            "NumPy has no equivalent of {}",
+            cls.name()?
+        )))
+    }
+}
@@ -1,1403 +1,1830 @@
-use super::{Endianness, NativeEndian, PyShaped};
+use super::{bytes_of, scalar_dtype, Endianness, Overflow, PyShaped};
+use crate::errors::{BUFFER_SIZE_ERROR, VALUE_RANGE_ERROR};
 use half::f16;
 use pyo3::basic::CompareOp;
+use pyo3::exceptions::{PyOverflowError, PyTypeError};
 use pyo3::prelude::*;
-use pyo3::types::{PyBytes, PyLong, PyType};
-use std::cell::RefCell;
+use pyo3::types::PyType;
 
-/// Converts a slice to an array of the specified size
+/// Converts a slice to an array of the specified size.
+/// Views are always exactly as large as their type, so this only fails if that invariant is broken.
 fn buffer_alias<const N: usize>(buffer: &[u8]) -> &[u8; N] {
     buffer.try_into().expect("slice with incorrect length")
 }
 
-#[pyclass(module = "_cerialize", name = "boolean", subclass, weakref, extends=PyShaped)]
-pub struct Bool {
-    buffer: RefCell<[u8; std::mem::size_of::<bool>()]>,
-    endianness: Endianness,
+/// Converts bytes given by the user to the packed representation of `type_name`
+fn packed_buffer<const N: usize>(type_name: &str, buffer: &[u8]) -> PyResult<[u8; N]> {
+    buffer.try_into().map_err(|_| {
+        BUFFER_SIZE_ERROR.new_err(format!(
+            "{type_name} requires a buffer of exactly {N} bytes, got {}",
+            buffer.len()
+        ))
+    })
+}
+
+fn range_error(
+    value: &PyAny,
+    type_name: &str,
+    min: impl std::fmt::Display,
+    max: impl std::fmt::Display,
+) -> PyErr {
+    VALUE_RANGE_ERROR.new_err(format!(
+        "{value} is out of range for {type_name} ({min}..={max})"
+    ))
 }
 
+fn unsupported_value(type_name: &str, value: &PyAny) -> PyResult<PyErr> {
+    Ok(PyTypeError::new_err(format!(
+        "{type_name} can't be created from a value of type {}",
+        value.get_type().name()?
+    )))
+}
+
+#[pyclass(module = "_cerialize", name = "boolean", subclass, weakref, extends=PyShaped)]
+pub struct Bool();
+
 impl Bool {
     // type DataType = bool;
 
+    const NAME: &'static str = "boolean";
     const PACKED_SIZE: usize = std::mem::size_of::<bool>();
+    const ALIGNMENT: usize = std::mem::align_of::<bool>();
 
     fn to_bytes(_endianness: &Endianness, value: bool) -> [u8; Self::PACKED_SIZE] {
-        unsafe { std::mem::transmute_copy::<bool, [u8; Self::PACKED_SIZE]>(&value) }
+        [value as u8]
     }
 
-    fn from_bytes(_endianness: &Endianness, buffer: &RefCell<[u8; Self::PACKED_SIZE]>) -> bool {
-        unsafe { std::mem::transmute_copy::<[u8; Self::PACKED_SIZE], bool>(&buffer.borrow()) }
+    fn from_bytes(_endianness: &Endianness, buffer: &[u8]) -> bool {
+        // Any non-zero byte is considered to be true, the same as in C
+        buffer_alias::<{ Self::PACKED_SIZE }>(buffer)[0] != 0
     }
 
-    fn value(&self) -> bool {
-        Self::from_bytes(&self.endianness, &self.buffer)
+    fn value(py: Python<'_>, shaped: &PyShaped) -> bool {
+        shaped
+            .buffer
+            .read(py, |buffer| Self::from_bytes(&shaped.endianness, buffer))
     }
 }
 
 #[pymethods]
 impl Bool {
     #[new]
-    fn new(value: Option<&PyAny>, endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        match value {
+    fn new(
+        py: Python<'_>,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or(Endianness::Native);
+        let buffer = match value {
             Some(value) => {
-                if let Ok(literal) = value.downcast::<PyLong>() {
-                    let literal = literal.extract::<bool>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, literal)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
-                } else if let Ok(buffer) = value.downcast::<PyBytes>() {
-                    let buffer = buffer.extract::<&[u8]>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(*buffer_alias::<{ Self::PACKED_SIZE }>(buffer)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                if value.hasattr("__bool__")? {
+                    let literal = value.is_true()?;
+                    Self::to_bytes(&endianness, literal)
+                } else if let Some(buffer) = bytes_of(value)? {
+                    packed_buffer::<{ Self::PACKED_SIZE }>(Self::NAME, &buffer)?
                 } else {
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, bool::default())),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                    return Err(unsupported_value(Self::NAME, value)?);
                 }
             }
-            None => (
-                Self {
-                    buffer: RefCell::new(Self::to_bytes(&endianness, bool::default())),
-                    endianness,
-                },
-                PyShaped::new(),
-            ),
-        }
+            None => Self::to_bytes(&endianness, bool::default()),
+        };
+        Ok((
+            Self(),
+            PyShaped::with_buffer(py, buffer.to_vec(), endianness)?,
+        ))
     }
 
     fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
         let class_name: &str = slf.get_type().name()?;
-        let value = slf.borrow().value();
+        let value = Self::value(slf.py(), slf.borrow().as_ref());
         Ok(format!("{class_name}({})", value))
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+    fn __richcmp__(slf: PyRef<'_, Self>, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let py = slf.py();
+        let (value, other) = (
+            Self::value(py, slf.as_ref()),
+            Self::value(py, other.as_ref()),
+        );
         match op {
-            CompareOp::Lt => Ok(!self.value() & other.value()),
-            CompareOp::Le => Ok(self.value() <= other.value()),
-            CompareOp::Eq => Ok(self.value() == other.value()),
-            CompareOp::Ne => Ok(self.value() != other.value()),
-            CompareOp::Gt => Ok(self.value() & !other.value()),
-            CompareOp::Ge => Ok(self.value() >= other.value()),
+            CompareOp::Lt => Ok(!value & other),
+            CompareOp::Le => Ok(value <= other),
+            CompareOp::Eq => Ok(value == other),
+            CompareOp::Ne => Ok(value != other),
+            CompareOp::Gt => Ok(value & !other),
+            CompareOp::Ge => Ok(value >= other),
         }
     }
 
+    fn __bool__(slf: PyRef<'_, Self>) -> bool {
+        Self::value(slf.py(), slf.as_ref())
+    }
+
     #[classmethod]
     fn __packed_size__(_cls: &PyType) -> PyResult<usize> {
         Ok(Self::PACKED_SIZE)
     }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(Self::ALIGNMENT)
+    }
+
+    /// Returns the equivalent NumPy dtype, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        scalar_dtype(cls, 'b', Self::PACKED_SIZE, endianness.unwrap_or_default())
+    }
 }
 
 #[pyclass(module = "_cerialize", name = "i8", subclass, weakref, extends=PyShaped)]
-pub struct Int8 {
-    buffer: RefCell<[u8; std::mem::size_of::<i8>()]>,
-    endianness: Endianness,
-}
+pub struct Int8();
 
 impl Int8 {
     // type DataType = i8;
 
+    const NAME: &'static str = "i8";
     const PACKED_SIZE: usize = std::mem::size_of::<i8>();
+    const ALIGNMENT: usize = std::mem::align_of::<i8>();
 
     fn to_bytes(endianness: &Endianness, value: i8) -> [u8; Self::PACKED_SIZE] {
         match endianness {
-            Endianness::Native(_) => value.to_ne_bytes(),
-            Endianness::Big(_) => value.to_be_bytes(),
-            Endianness::Little(_) => value.to_le_bytes(),
+            Endianness::Native => value.to_ne_bytes(),
+            Endianness::Big => value.to_be_bytes(),
+            Endianness::Little => value.to_le_bytes(),
         }
     }
 
-    fn from_bytes(endianness: &Endianness, buffer: &RefCell<[u8; Self::PACKED_SIZE]>) -> i8 {
+    fn from_bytes(endianness: &Endianness, buffer: &[u8]) -> i8 {
+        let buffer = *buffer_alias::<{ Self::PACKED_SIZE }>(buffer);
         match endianness {
-            Endianness::Native(_) => i8::from_ne_bytes(*buffer.borrow()),
-            Endianness::Big(_) => i8::from_be_bytes(*buffer.borrow()),
-            Endianness::Little(_) => i8::from_le_bytes(*buffer.borrow()),
+            Endianness::Native => i8::from_ne_bytes(buffer),
+            Endianness::Big => i8::from_be_bytes(buffer),
+            Endianness::Little => i8::from_le_bytes(buffer),
         }
     }
 
-    fn value(&self) -> i8 {
-        Self::from_bytes(&self.endianness, &self.buffer)
+    /// Converts a Python int, handling values which don't fit according to `overflow`
+    fn convert(value: &PyAny, overflow: Overflow) -> PyResult<i8> {
+        let err = match value.extract::<i8>() {
+            Ok(literal) => return Ok(literal),
+            Err(err) => err,
+        };
+        if !err.is_instance_of::<PyOverflowError>(value.py()) {
+            return Err(err);
+        }
+
+        match overflow {
+            Overflow::Error => Err(range_error(value, Self::NAME, i8::MIN, i8::MAX)),
+            Overflow::Wrap => {
+                // Masking in Python keeps the bits a two's complement representation of the value would have
+                let mask = u128::MAX >> (128 - 8 * Self::PACKED_SIZE);
+                let bits = value
+                    .call_method0("__index__")?
+                    .call_method1("__and__", (mask,))?
+                    .extract::<u128>()?;
+                Ok(bits as i8)
+            }
+            Overflow::Saturate => match value.lt(0)? {
+                true => Ok(i8::MIN),
+                false => Ok(i8::MAX),
+            },
+        }
+    }
+
+    fn value(py: Python<'_>, shaped: &PyShaped) -> i8 {
+        shaped
+            .buffer
+            .read(py, |buffer| Self::from_bytes(&shaped.endianness, buffer))
     }
 }
 
 #[pymethods]
 impl Int8 {
     #[new]
-    fn new(value: Option<&PyAny>, endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        match value {
+    #[pyo3(signature = (value = None, endianness = None, overflow = None))]
+    fn new(
+        py: Python<'_>,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+        overflow: Option<Overflow>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or(Endianness::Native);
+        let buffer = match value {
             Some(value) => {
-                if let Ok(literal) = value.downcast::<PyLong>() {
-                    let literal = literal.extract::<i8>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, literal)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
-                } else if let Ok(buffer) = value.downcast::<PyBytes>() {
-                    let buffer = buffer.extract::<&[u8]>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(*buffer_alias::<{ Self::PACKED_SIZE }>(buffer)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                if value.hasattr("__index__")? {
+                    let literal = Self::convert(value, overflow.unwrap_or_default())?;
+                    Self::to_bytes(&endianness, literal)
+                } else if let Some(buffer) = bytes_of(value)? {
+                    packed_buffer::<{ Self::PACKED_SIZE }>(Self::NAME, &buffer)?
                 } else {
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, i8::default())),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                    return Err(unsupported_value(Self::NAME, value)?);
                 }
             }
-            None => (
-                Self {
-                    buffer: RefCell::new(Self::to_bytes(&endianness, i8::default())),
-                    endianness,
-                },
-                PyShaped::new(),
-            ),
-        }
+            None => Self::to_bytes(&endianness, i8::default()),
+        };
+        Ok((
+            Self(),
+            PyShaped::with_buffer(py, buffer.to_vec(), endianness)?,
+        ))
     }
 
     fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
         let class_name: &str = slf.get_type().name()?;
-        let value = slf.borrow().value();
+        let value = Self::value(slf.py(), slf.borrow().as_ref());
         Ok(format!("{class_name}({})", value))
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+    fn __richcmp__(slf: PyRef<'_, Self>, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let py = slf.py();
+        let (value, other) = (
+            Self::value(py, slf.as_ref()),
+            Self::value(py, other.as_ref()),
+        );
         match op {
-            CompareOp::Lt => Ok(self.value() < other.value()),
-            CompareOp::Le => Ok(self.value() <= other.value()),
-            CompareOp::Eq => Ok(self.value() == other.value()),
-            CompareOp::Ne => Ok(self.value() != other.value()),
-            CompareOp::Gt => Ok(self.value() > other.value()),
-            CompareOp::Ge => Ok(self.value() >= other.value()),
+            CompareOp::Lt => Ok(value < other),
+            CompareOp::Le => Ok(value <= other),
+            CompareOp::Eq => Ok(value == other),
+            CompareOp::Ne => Ok(value != other),
+            CompareOp::Gt => Ok(value > other),
+            CompareOp::Ge => Ok(value >= other),
         }
     }
 
+    fn __index__(slf: PyRef<'_, Self>) -> i8 {
+        Self::value(slf.py(), slf.as_ref())
+    }
+
     #[classmethod]
     fn __packed_size__(_cls: &PyType) -> PyResult<usize> {
         Ok(Self::PACKED_SIZE)
     }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(Self::ALIGNMENT)
+    }
+
+    /// Returns the equivalent NumPy dtype, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let kind = 'i';
+        scalar_dtype(cls, kind, Self::PACKED_SIZE, endianness.unwrap_or_default())
+    }
 }
 
 #[pyclass(module = "_cerialize", name = "i16", subclass, weakref, extends=PyShaped)]
-pub struct Int16 {
-    buffer: RefCell<[u8; std::mem::size_of::<i16>()]>,
-    endianness: Endianness,
-}
+pub struct Int16();
 
 impl Int16 {
     // type DataType = i16;
 
+    const NAME: &'static str = "i16";
     const PACKED_SIZE: usize = std::mem::size_of::<i16>();
+    const ALIGNMENT: usize = std::mem::align_of::<i16>();
 
     fn to_bytes(endianness: &Endianness, value: i16) -> [u8; Self::PACKED_SIZE] {
         match endianness {
-            Endianness::Native(_) => value.to_ne_bytes(),
-            Endianness::Big(_) => value.to_be_bytes(),
-            Endianness::Little(_) => value.to_le_bytes(),
+            Endianness::Native => value.to_ne_bytes(),
+            Endianness::Big => value.to_be_bytes(),
+            Endianness::Little => value.to_le_bytes(),
         }
     }
 
-    fn from_bytes(endianness: &Endianness, buffer: &RefCell<[u8; Self::PACKED_SIZE]>) -> i16 {
+    fn from_bytes(endianness: &Endianness, buffer: &[u8]) -> i16 {
+        let buffer = *buffer_alias::<{ Self::PACKED_SIZE }>(buffer);
         match endianness {
-            Endianness::Native(_) => i16::from_ne_bytes(*buffer.borrow()),
-            Endianness::Big(_) => i16::from_be_bytes(*buffer.borrow()),
-            Endianness::Little(_) => i16::from_le_bytes(*buffer.borrow()),
+            Endianness::Native => i16::from_ne_bytes(buffer),
+            Endianness::Big => i16::from_be_bytes(buffer),
+            Endianness::Little => i16::from_le_bytes(buffer),
+        }
+    }
+
+    /// Converts a Python int, handling values which don't fit according to `overflow`
+    fn convert(value: &PyAny, overflow: Overflow) -> PyResult<i16> {
+        let err = match value.extract::<i16>() {
+            Ok(literal) => return Ok(literal),
+            Err(err) => err,
+        };
+        if !err.is_instance_of::<PyOverflowError>(value.py()) {
+            return Err(err);
+        }
+
+        match overflow {
+            Overflow::Error => Err(range_error(value, Self::NAME, i16::MIN, i16::MAX)),
+            Overflow::Wrap => {
+                // Masking in Python keeps the bits a two's complement representation of the value would have
+                let mask = u128::MAX >> (128 - 8 * Self::PACKED_SIZE);
+                let bits = value
+                    .call_method0("__index__")?
+                    .call_method1("__and__", (mask,))?
+                    .extract::<u128>()?;
+                Ok(bits as i16)
+            }
+            Overflow::Saturate => match value.lt(0)? {
+                true => Ok(i16::MIN),
+                false => Ok(i16::MAX),
+            },
         }
     }
 
-    fn value(&self) -> i16 {
-        Self::from_bytes(&self.endianness, &self.buffer)
+    fn value(py: Python<'_>, shaped: &PyShaped) -> i16 {
+        shaped
+            .buffer
+            .read(py, |buffer| Self::from_bytes(&shaped.endianness, buffer))
     }
 }
 
 #[pymethods]
 impl Int16 {
     #[new]
-    fn new(value: Option<&PyAny>, endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        match value {
+    #[pyo3(signature = (value = None, endianness = None, overflow = None))]
+    fn new(
+        py: Python<'_>,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+        overflow: Option<Overflow>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or(Endianness::Native);
+        let buffer = match value {
             Some(value) => {
-                if let Ok(literal) = value.downcast::<PyLong>() {
-                    let literal = literal.extract::<i16>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, literal)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
-                } else if let Ok(buffer) = value.downcast::<PyBytes>() {
-                    let buffer = buffer.extract::<&[u8]>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(*buffer_alias::<{ Self::PACKED_SIZE }>(buffer)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                if value.hasattr("__index__")? {
+                    let literal = Self::convert(value, overflow.unwrap_or_default())?;
+                    Self::to_bytes(&endianness, literal)
+                } else if let Some(buffer) = bytes_of(value)? {
+                    packed_buffer::<{ Self::PACKED_SIZE }>(Self::NAME, &buffer)?
                 } else {
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, i16::default())),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                    return Err(unsupported_value(Self::NAME, value)?);
                 }
             }
-            None => (
-                Self {
-                    buffer: RefCell::new(Self::to_bytes(&endianness, i16::default())),
-                    endianness,
-                },
-                PyShaped::new(),
-            ),
-        }
+            None => Self::to_bytes(&endianness, i16::default()),
+        };
+        Ok((
+            Self(),
+            PyShaped::with_buffer(py, buffer.to_vec(), endianness)?,
+        ))
     }
 
     fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
         let class_name: &str = slf.get_type().name()?;
-        let value = slf.borrow().value();
+        let value = Self::value(slf.py(), slf.borrow().as_ref());
         Ok(format!("{class_name}({})", value))
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+    fn __richcmp__(slf: PyRef<'_, Self>, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let py = slf.py();
+        let (value, other) = (
+            Self::value(py, slf.as_ref()),
+            Self::value(py, other.as_ref()),
+        );
         match op {
-            CompareOp::Lt => Ok(self.value() < other.value()),
-            CompareOp::Le => Ok(self.value() <= other.value()),
-            CompareOp::Eq => Ok(self.value() == other.value()),
-            CompareOp::Ne => Ok(self.value() != other.value()),
-            CompareOp::Gt => Ok(self.value() > other.value()),
-            CompareOp::Ge => Ok(self.value() >= other.value()),
+            CompareOp::Lt => Ok(value < other),
+            CompareOp::Le => Ok(value <= other),
+            CompareOp::Eq => Ok(value == other),
+            CompareOp::Ne => Ok(value != other),
+            CompareOp::Gt => Ok(value > other),
+            CompareOp::Ge => Ok(value >= other),
         }
     }
 
+    fn __index__(slf: PyRef<'_, Self>) -> i16 {
+        Self::value(slf.py(), slf.as_ref())
+    }
+
     #[classmethod]
     fn __packed_size__(_cls: &PyType) -> PyResult<usize> {
         Ok(Self::PACKED_SIZE)
     }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(Self::ALIGNMENT)
+    }
+
+    /// Returns the equivalent NumPy dtype, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let kind = 'i';
+        scalar_dtype(cls, kind, Self::PACKED_SIZE, endianness.unwrap_or_default())
+    }
 }
 
 #[pyclass(module = "_cerialize", name = "i32", subclass, weakref, extends=PyShaped)]
-pub struct Int32 {
-    buffer: RefCell<[u8; std::mem::size_of::<i32>()]>,
-    endianness: Endianness,
-}
+pub struct Int32();
 
 impl Int32 {
     // type DataType = i32;
 
+    const NAME: &'static str = "i32";
     const PACKED_SIZE: usize = std::mem::size_of::<i32>();
+    const ALIGNMENT: usize = std::mem::align_of::<i32>();
 
     fn to_bytes(endianness: &Endianness, value: i32) -> [u8; Self::PACKED_SIZE] {
         match endianness {
-            Endianness::Native(_) => value.to_ne_bytes(),
-            Endianness::Big(_) => value.to_be_bytes(),
-            Endianness::Little(_) => value.to_le_bytes(),
+            Endianness::Native => value.to_ne_bytes(),
+            Endianness::Big => value.to_be_bytes(),
+            Endianness::Little => value.to_le_bytes(),
         }
     }
 
-    fn from_bytes(endianness: &Endianness, buffer: &RefCell<[u8; Self::PACKED_SIZE]>) -> i32 {
+    fn from_bytes(endianness: &Endianness, buffer: &[u8]) -> i32 {
+        let buffer = *buffer_alias::<{ Self::PACKED_SIZE }>(buffer);
         match endianness {
-            Endianness::Native(_) => i32::from_ne_bytes(*buffer.borrow()),
-            Endianness::Big(_) => i32::from_be_bytes(*buffer.borrow()),
-            Endianness::Little(_) => i32::from_le_bytes(*buffer.borrow()),
+            Endianness::Native => i32::from_ne_bytes(buffer),
+            Endianness::Big => i32::from_be_bytes(buffer),
+            Endianness::Little => i32::from_le_bytes(buffer),
         }
     }
 
-    fn value(&self) -> i32 {
-        Self::from_bytes(&self.endianness, &self.buffer)
+    /// Converts a Python int, handling values which don't fit according to `overflow`
+    fn convert(value: &PyAny, overflow: Overflow) -> PyResult<i32> {
+        let err = match value.extract::<i32>() {
+            Ok(literal) => return Ok(literal),
+            Err(err) => err,
+        };
+        if !err.is_instance_of::<PyOverflowError>(value.py()) {
+            return Err(err);
+        }
+
+        match overflow {
+            Overflow::Error => Err(range_error(value, Self::NAME, i32::MIN, i32::MAX)),
+            Overflow::Wrap => {
+                // Masking in Python keeps the bits a two's complement representation of the value would have
+                let mask = u128::MAX >> (128 - 8 * Self::PACKED_SIZE);
+                let bits = value
+                    .call_method0("__index__")?
+                    .call_method1("__and__", (mask,))?
+                    .extract::<u128>()?;
+                Ok(bits as i32)
+            }
+            Overflow::Saturate => match value.lt(0)? {
+                true => Ok(i32::MIN),
+                false => Ok(i32::MAX),
+            },
+        }
+    }
+
+    fn value(py: Python<'_>, shaped: &PyShaped) -> i32 {
+        shaped
+            .buffer
+            .read(py, |buffer| Self::from_bytes(&shaped.endianness, buffer))
     }
 }
 
 #[pymethods]
 impl Int32 {
     #[new]
-    fn new(value: Option<&PyAny>, endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        match value {
+    #[pyo3(signature = (value = None, endianness = None, overflow = None))]
+    fn new(
+        py: Python<'_>,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+        overflow: Option<Overflow>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or(Endianness::Native);
+        let buffer = match value {
             Some(value) => {
-                if let Ok(literal) = value.downcast::<PyLong>() {
-                    let literal = literal.extract::<i32>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, literal)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
-                } else if let Ok(buffer) = value.downcast::<PyBytes>() {
-                    let buffer = buffer.extract::<&[u8]>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(*buffer_alias::<{ Self::PACKED_SIZE }>(buffer)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                if value.hasattr("__index__")? {
+                    let literal = Self::convert(value, overflow.unwrap_or_default())?;
+                    Self::to_bytes(&endianness, literal)
+                } else if let Some(buffer) = bytes_of(value)? {
+                    packed_buffer::<{ Self::PACKED_SIZE }>(Self::NAME, &buffer)?
                 } else {
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, i32::default())),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                    return Err(unsupported_value(Self::NAME, value)?);
                 }
             }
-            None => (
-                Self {
-                    buffer: RefCell::new(Self::to_bytes(&endianness, i32::default())),
-                    endianness,
-                },
-                PyShaped::new(),
-            ),
-        }
+            None => Self::to_bytes(&endianness, i32::default()),
+        };
+        Ok((
+            Self(),
+            PyShaped::with_buffer(py, buffer.to_vec(), endianness)?,
+        ))
     }
 
     fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
         let class_name: &str = slf.get_type().name()?;
-        let value = slf.borrow().value();
+        let value = Self::value(slf.py(), slf.borrow().as_ref());
         Ok(format!("{class_name}({})", value))
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+    fn __richcmp__(slf: PyRef<'_, Self>, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let py = slf.py();
+        let (value, other) = (
+            Self::value(py, slf.as_ref()),
+            Self::value(py, other.as_ref()),
+        );
         match op {
-            CompareOp::Lt => Ok(self.value() < other.value()),
-            CompareOp::Le => Ok(self.value() <= other.value()),
-            CompareOp::Eq => Ok(self.value() == other.value()),
-            CompareOp::Ne => Ok(self.value() != other.value()),
-            CompareOp::Gt => Ok(self.value() > other.value()),
-            CompareOp::Ge => Ok(self.value() >= other.value()),
+            CompareOp::Lt => Ok(value < other),
+            CompareOp::Le => Ok(value <= other),
+            CompareOp::Eq => Ok(value == other),
+            CompareOp::Ne => Ok(value != other),
+            CompareOp::Gt => Ok(value > other),
+            CompareOp::Ge => Ok(value >= other),
         }
     }
 
+    fn __index__(slf: PyRef<'_, Self>) -> i32 {
+        Self::value(slf.py(), slf.as_ref())
+    }
+
     #[classmethod]
     fn __packed_size__(_cls: &PyType) -> PyResult<usize> {
         Ok(Self::PACKED_SIZE)
     }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(Self::ALIGNMENT)
+    }
+
+    /// Returns the equivalent NumPy dtype, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let kind = 'i';
+        scalar_dtype(cls, kind, Self::PACKED_SIZE, endianness.unwrap_or_default())
+    }
 }
 
 #[pyclass(module = "_cerialize", name = "i64", subclass, weakref, extends=PyShaped)]
-pub struct Int64 {
-    buffer: RefCell<[u8; std::mem::size_of::<i64>()]>,
-    endianness: Endianness,
-}
+pub struct Int64();
 
 impl Int64 {
     // type DataType = i64;
 
+    const NAME: &'static str = "i64";
     const PACKED_SIZE: usize = std::mem::size_of::<i64>();
+    const ALIGNMENT: usize = std::mem::align_of::<i64>();
 
     fn to_bytes(endianness: &Endianness, value: i64) -> [u8; Self::PACKED_SIZE] {
         match endianness {
-            Endianness::Native(_) => value.to_ne_bytes(),
-            Endianness::Big(_) => value.to_be_bytes(),
-            Endianness::Little(_) => value.to_le_bytes(),
+            Endianness::Native => value.to_ne_bytes(),
+            Endianness::Big => value.to_be_bytes(),
+            Endianness::Little => value.to_le_bytes(),
         }
     }
 
-    fn from_bytes(endianness: &Endianness, buffer: &RefCell<[u8; Self::PACKED_SIZE]>) -> i64 {
+    fn from_bytes(endianness: &Endianness, buffer: &[u8]) -> i64 {
+        let buffer = *buffer_alias::<{ Self::PACKED_SIZE }>(buffer);
         match endianness {
-            Endianness::Native(_) => i64::from_ne_bytes(*buffer.borrow()),
-            Endianness::Big(_) => i64::from_be_bytes(*buffer.borrow()),
-            Endianness::Little(_) => i64::from_le_bytes(*buffer.borrow()),
+            Endianness::Native => i64::from_ne_bytes(buffer),
+            Endianness::Big => i64::from_be_bytes(buffer),
+            Endianness::Little => i64::from_le_bytes(buffer),
         }
     }
 
-    fn value(&self) -> i64 {
-        Self::from_bytes(&self.endianness, &self.buffer)
+    /// Converts a Python int, handling values which don't fit according to `overflow`
+    fn convert(value: &PyAny, overflow: Overflow) -> PyResult<i64> {
+        let err = match value.extract::<i64>() {
+            Ok(literal) => return Ok(literal),
+            Err(err) => err,
+        };
+        if !err.is_instance_of::<PyOverflowError>(value.py()) {
+            return Err(err);
+        }
+
+        match overflow {
+            Overflow::Error => Err(range_error(value, Self::NAME, i64::MIN, i64::MAX)),
+            Overflow::Wrap => {
+                // Masking in Python keeps the bits a two's complement representation of the value would have
+                let mask = u128::MAX >> (128 - 8 * Self::PACKED_SIZE);
+                let bits = value
+                    .call_method0("__index__")?
+                    .call_method1("__and__", (mask,))?
+                    .extract::<u128>()?;
+                Ok(bits as i64)
+            }
+            Overflow::Saturate => match value.lt(0)? {
+                true => Ok(i64::MIN),
+                false => Ok(i64::MAX),
+            },
+        }
+    }
+
+    fn value(py: Python<'_>, shaped: &PyShaped) -> i64 {
+        shaped
+            .buffer
+            .read(py, |buffer| Self::from_bytes(&shaped.endianness, buffer))
     }
 }
 
 #[pymethods]
 impl Int64 {
     #[new]
-    fn new(value: Option<&PyAny>, endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        match value {
+    #[pyo3(signature = (value = None, endianness = None, overflow = None))]
+    fn new(
+        py: Python<'_>,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+        overflow: Option<Overflow>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or(Endianness::Native);
+        let buffer = match value {
             Some(value) => {
-                if let Ok(literal) = value.downcast::<PyLong>() {
-                    let literal = literal.extract::<i64>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, literal)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
-                } else if let Ok(buffer) = value.downcast::<PyBytes>() {
-                    let buffer = buffer.extract::<&[u8]>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(*buffer_alias::<{ Self::PACKED_SIZE }>(buffer)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                if value.hasattr("__index__")? {
+                    let literal = Self::convert(value, overflow.unwrap_or_default())?;
+                    Self::to_bytes(&endianness, literal)
+                } else if let Some(buffer) = bytes_of(value)? {
+                    packed_buffer::<{ Self::PACKED_SIZE }>(Self::NAME, &buffer)?
                 } else {
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, 0)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                    return Err(unsupported_value(Self::NAME, value)?);
                 }
             }
-            None => (
-                Self {
-                    buffer: RefCell::new(Self::to_bytes(&endianness, 0)),
-                    endianness,
-                },
-                PyShaped::new(),
-            ),
-        }
+            None => Self::to_bytes(&endianness, i64::default()),
+        };
+        Ok((
+            Self(),
+            PyShaped::with_buffer(py, buffer.to_vec(), endianness)?,
+        ))
     }
 
     fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
         let class_name: &str = slf.get_type().name()?;
-        let value = slf.borrow().value();
+        let value = Self::value(slf.py(), slf.borrow().as_ref());
         Ok(format!("{class_name}({})", value))
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+    fn __richcmp__(slf: PyRef<'_, Self>, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let py = slf.py();
+        let (value, other) = (
+            Self::value(py, slf.as_ref()),
+            Self::value(py, other.as_ref()),
+        );
         match op {
-            CompareOp::Lt => Ok(self.value() < other.value()),
-            CompareOp::Le => Ok(self.value() <= other.value()),
-            CompareOp::Eq => Ok(self.value() == other.value()),
-            CompareOp::Ne => Ok(self.value() != other.value()),
-            CompareOp::Gt => Ok(self.value() > other.value()),
-            CompareOp::Ge => Ok(self.value() >= other.value()),
+            CompareOp::Lt => Ok(value < other),
+            CompareOp::Le => Ok(value <= other),
+            CompareOp::Eq => Ok(value == other),
+            CompareOp::Ne => Ok(value != other),
+            CompareOp::Gt => Ok(value > other),
+            CompareOp::Ge => Ok(value >= other),
         }
     }
 
+    fn __index__(slf: PyRef<'_, Self>) -> i64 {
+        Self::value(slf.py(), slf.as_ref())
+    }
+
     #[classmethod]
     fn __packed_size__(_cls: &PyType) -> PyResult<usize> {
         Ok(Self::PACKED_SIZE)
     }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(Self::ALIGNMENT)
+    }
+
+    /// Returns the equivalent NumPy dtype, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let kind = 'i';
+        scalar_dtype(cls, kind, Self::PACKED_SIZE, endianness.unwrap_or_default())
+    }
 }
 
 #[pyclass(module = "_cerialize", name = "i128", subclass, weakref, extends=PyShaped)]
-pub struct Int128 {
-    buffer: RefCell<[u8; std::mem::size_of::<i128>()]>,
-    endianness: Endianness,
-}
+pub struct Int128();
 
 impl Int128 {
     // type DataType = i128;
 
+    const NAME: &'static str = "i128";
     const PACKED_SIZE: usize = std::mem::size_of::<i128>();
+    const ALIGNMENT: usize = std::mem::align_of::<i128>();
 
     fn to_bytes(endianness: &Endianness, value: i128) -> [u8; Self::PACKED_SIZE] {
         match endianness {
-            Endianness::Native(_) => value.to_ne_bytes(),
-            Endianness::Big(_) => value.to_be_bytes(),
-            Endianness::Little(_) => value.to_le_bytes(),
+            Endianness::Native => value.to_ne_bytes(),
+            Endianness::Big => value.to_be_bytes(),
+            Endianness::Little => value.to_le_bytes(),
         }
     }
 
-    fn from_bytes(endianness: &Endianness, buffer: &RefCell<[u8; Self::PACKED_SIZE]>) -> i128 {
+    fn from_bytes(endianness: &Endianness, buffer: &[u8]) -> i128 {
+        let buffer = *buffer_alias::<{ Self::PACKED_SIZE }>(buffer);
         match endianness {
-            Endianness::Native(_) => i128::from_ne_bytes(*buffer.borrow()),
-            Endianness::Big(_) => i128::from_be_bytes(*buffer.borrow()),
-            Endianness::Little(_) => i128::from_le_bytes(*buffer.borrow()),
+            Endianness::Native => i128::from_ne_bytes(buffer),
+            Endianness::Big => i128::from_be_bytes(buffer),
+            Endianness::Little => i128::from_le_bytes(buffer),
         }
     }
 
-    fn value(&self) -> i128 {
-        Self::from_bytes(&self.endianness, &self.buffer)
+    /// Converts a Python int, handling values which don't fit according to `overflow`
+    fn convert(value: &PyAny, overflow: Overflow) -> PyResult<i128> {
+        let err = match value.extract::<i128>() {
+            Ok(literal) => return Ok(literal),
+            Err(err) => err,
+        };
+        if !err.is_instance_of::<PyOverflowError>(value.py()) {
+            return Err(err);
+        }
+
+        match overflow {
+            Overflow::Error => Err(range_error(value, Self::NAME, i128::MIN, i128::MAX)),
+            Overflow::Wrap => {
+                // Masking in Python keeps the bits a two's complement representation of the value would have
+                let mask = u128::MAX >> (128 - 8 * Self::PACKED_SIZE);
+                let bits = value
+                    .call_method0("__index__")?
+                    .call_method1("__and__", (mask,))?
+                    .extract::<u128>()?;
+                Ok(bits as i128)
+            }
+            Overflow::Saturate => match value.lt(0)? {
+                true => Ok(i128::MIN),
+                false => Ok(i128::MAX),
+            },
+        }
+    }
+
+    fn value(py: Python<'_>, shaped: &PyShaped) -> i128 {
+        shaped
+            .buffer
+            .read(py, |buffer| Self::from_bytes(&shaped.endianness, buffer))
     }
 }
 
 #[pymethods]
 impl Int128 {
     #[new]
-    fn new(value: Option<&PyAny>, endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        match value {
+    #[pyo3(signature = (value = None, endianness = None, overflow = None))]
+    fn new(
+        py: Python<'_>,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+        overflow: Option<Overflow>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or(Endianness::Native);
+        let buffer = match value {
             Some(value) => {
-                if let Ok(literal) = value.downcast::<PyLong>() {
-                    let literal = literal.extract::<i128>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, literal)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
-                } else if let Ok(buffer) = value.downcast::<PyBytes>() {
-                    let buffer = buffer.extract::<&[u8]>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(*buffer_alias::<{ Self::PACKED_SIZE }>(buffer)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                if value.hasattr("__index__")? {
+                    let literal = Self::convert(value, overflow.unwrap_or_default())?;
+                    Self::to_bytes(&endianness, literal)
+                } else if let Some(buffer) = bytes_of(value)? {
+                    packed_buffer::<{ Self::PACKED_SIZE }>(Self::NAME, &buffer)?
                 } else {
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, i128::default())),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                    return Err(unsupported_value(Self::NAME, value)?);
                 }
             }
-            None => (
-                Self {
-                    buffer: RefCell::new(Self::to_bytes(&endianness, i128::default())),
-                    endianness,
-                },
-                PyShaped::new(),
-            ),
-        }
+            None => Self::to_bytes(&endianness, i128::default()),
+        };
+        Ok((
+            Self(),
+            PyShaped::with_buffer(py, buffer.to_vec(), endianness)?,
+        ))
     }
 
     fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
         let class_name: &str = slf.get_type().name()?;
-        let value = slf.borrow().value();
+        let value = Self::value(slf.py(), slf.borrow().as_ref());
         Ok(format!("{class_name}({})", value))
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+    fn __richcmp__(slf: PyRef<'_, Self>, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let py = slf.py();
+        let (value, other) = (
+            Self::value(py, slf.as_ref()),
+            Self::value(py, other.as_ref()),
+        );
         match op {
-            CompareOp::Lt => Ok(self.value() < other.value()),
-            CompareOp::Le => Ok(self.value() <= other.value()),
-            CompareOp::Eq => Ok(self.value() == other.value()),
-            CompareOp::Ne => Ok(self.value() != other.value()),
-            CompareOp::Gt => Ok(self.value() > other.value()),
-            CompareOp::Ge => Ok(self.value() >= other.value()),
+            CompareOp::Lt => Ok(value < other),
+            CompareOp::Le => Ok(value <= other),
+            CompareOp::Eq => Ok(value == other),
+            CompareOp::Ne => Ok(value != other),
+            CompareOp::Gt => Ok(value > other),
+            CompareOp::Ge => Ok(value >= other),
         }
     }
 
+    fn __index__(slf: PyRef<'_, Self>) -> i128 {
+        Self::value(slf.py(), slf.as_ref())
+    }
+
     #[classmethod]
     fn __packed_size__(_cls: &PyType) -> PyResult<usize> {
         Ok(Self::PACKED_SIZE)
     }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(Self::ALIGNMENT)
+    }
+
+    /// Returns the equivalent NumPy dtype, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let kind = 'i';
+        scalar_dtype(cls, kind, Self::PACKED_SIZE, endianness.unwrap_or_default())
+    }
 }
 
 #[pyclass(module = "_cerialize", name = "u8", subclass, weakref, extends=PyShaped)]
-pub struct Uint8 {
-    buffer: RefCell<[u8; std::mem::size_of::<u8>()]>,
-    endianness: Endianness,
-}
+pub struct Uint8();
 
 impl Uint8 {
     // type DataType = u8;
 
+    const NAME: &'static str = "u8";
     const PACKED_SIZE: usize = std::mem::size_of::<u8>();
+    const ALIGNMENT: usize = std::mem::align_of::<u8>();
 
     fn to_bytes(endianness: &Endianness, value: u8) -> [u8; Self::PACKED_SIZE] {
         match endianness {
-            Endianness::Native(_) => value.to_ne_bytes(),
-            Endianness::Big(_) => value.to_be_bytes(),
-            Endianness::Little(_) => value.to_le_bytes(),
+            Endianness::Native => value.to_ne_bytes(),
+            Endianness::Big => value.to_be_bytes(),
+            Endianness::Little => value.to_le_bytes(),
         }
     }
 
-    fn from_bytes(endianness: &Endianness, buffer: &RefCell<[u8; Self::PACKED_SIZE]>) -> u8 {
+    fn from_bytes(endianness: &Endianness, buffer: &[u8]) -> u8 {
+        let buffer = *buffer_alias::<{ Self::PACKED_SIZE }>(buffer);
         match endianness {
-            Endianness::Native(_) => u8::from_ne_bytes(*buffer.borrow()),
-            Endianness::Big(_) => u8::from_be_bytes(*buffer.borrow()),
-            Endianness::Little(_) => u8::from_le_bytes(*buffer.borrow()),
+            Endianness::Native => u8::from_ne_bytes(buffer),
+            Endianness::Big => u8::from_be_bytes(buffer),
+            Endianness::Little => u8::from_le_bytes(buffer),
+        }
+    }
+
+    /// Converts a Python int, handling values which don't fit according to `overflow`
+    fn convert(value: &PyAny, overflow: Overflow) -> PyResult<u8> {
+        let err = match value.extract::<u8>() {
+            Ok(literal) => return Ok(literal),
+            Err(err) => err,
+        };
+        if !err.is_instance_of::<PyOverflowError>(value.py()) {
+            return Err(err);
+        }
+
+        match overflow {
+            Overflow::Error => Err(range_error(value, Self::NAME, u8::MIN, u8::MAX)),
+            Overflow::Wrap => {
+                // Masking in Python keeps the bits a two's complement representation of the value would have
+                let mask = u128::MAX >> (128 - 8 * Self::PACKED_SIZE);
+                let bits = value
+                    .call_method0("__index__")?
+                    .call_method1("__and__", (mask,))?
+                    .extract::<u128>()?;
+                Ok(bits as u8)
+            }
+            Overflow::Saturate => match value.lt(0)? {
+                true => Ok(u8::MIN),
+                false => Ok(u8::MAX),
+            },
         }
     }
 
-    fn value(&self) -> u8 {
-        Self::from_bytes(&self.endianness, &self.buffer)
+    fn value(py: Python<'_>, shaped: &PyShaped) -> u8 {
+        shaped
+            .buffer
+            .read(py, |buffer| Self::from_bytes(&shaped.endianness, buffer))
     }
 }
 
 #[pymethods]
 impl Uint8 {
     #[new]
-    fn new(value: Option<&PyAny>, endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        match value {
+    #[pyo3(signature = (value = None, endianness = None, overflow = None))]
+    fn new(
+        py: Python<'_>,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+        overflow: Option<Overflow>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or(Endianness::Native);
+        let buffer = match value {
             Some(value) => {
-                if let Ok(literal) = value.downcast::<PyLong>() {
-                    let literal = literal.extract::<u8>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, literal)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
-                } else if let Ok(buffer) = value.downcast::<PyBytes>() {
-                    let buffer = buffer.extract::<&[u8]>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(*buffer_alias::<{ Self::PACKED_SIZE }>(buffer)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                if value.hasattr("__index__")? {
+                    let literal = Self::convert(value, overflow.unwrap_or_default())?;
+                    Self::to_bytes(&endianness, literal)
+                } else if let Some(buffer) = bytes_of(value)? {
+                    packed_buffer::<{ Self::PACKED_SIZE }>(Self::NAME, &buffer)?
                 } else {
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, u8::default())),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                    return Err(unsupported_value(Self::NAME, value)?);
                 }
             }
-            None => (
-                Self {
-                    buffer: RefCell::new(Self::to_bytes(&endianness, u8::default())),
-                    endianness,
-                },
-                PyShaped::new(),
-            ),
-        }
+            None => Self::to_bytes(&endianness, u8::default()),
+        };
+        Ok((
+            Self(),
+            PyShaped::with_buffer(py, buffer.to_vec(), endianness)?,
+        ))
     }
 
     fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
         let class_name: &str = slf.get_type().name()?;
-        let value = slf.borrow().value();
+        let value = Self::value(slf.py(), slf.borrow().as_ref());
         Ok(format!("{class_name}({})", value))
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+    fn __richcmp__(slf: PyRef<'_, Self>, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let py = slf.py();
+        let (value, other) = (
+            Self::value(py, slf.as_ref()),
+            Self::value(py, other.as_ref()),
+        );
         match op {
-            CompareOp::Lt => Ok(self.value() < other.value()),
-            CompareOp::Le => Ok(self.value() <= other.value()),
-            CompareOp::Eq => Ok(self.value() == other.value()),
-            CompareOp::Ne => Ok(self.value() != other.value()),
-            CompareOp::Gt => Ok(self.value() > other.value()),
-            CompareOp::Ge => Ok(self.value() >= other.value()),
+            CompareOp::Lt => Ok(value < other),
+            CompareOp::Le => Ok(value <= other),
+            CompareOp::Eq => Ok(value == other),
+            CompareOp::Ne => Ok(value != other),
+            CompareOp::Gt => Ok(value > other),
+            CompareOp::Ge => Ok(value >= other),
         }
     }
 
+    fn __index__(slf: PyRef<'_, Self>) -> u8 {
+        Self::value(slf.py(), slf.as_ref())
+    }
+
     #[classmethod]
     fn __packed_size__(_cls: &PyType) -> PyResult<usize> {
         Ok(Self::PACKED_SIZE)
     }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(Self::ALIGNMENT)
+    }
+
+    /// Returns the equivalent NumPy dtype, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let kind = 'u';
+        scalar_dtype(cls, kind, Self::PACKED_SIZE, endianness.unwrap_or_default())
+    }
 }
 
 #[pyclass(module = "_cerialize", name = "u16", subclass, weakref, extends=PyShaped)]
-pub struct Uint16 {
-    buffer: RefCell<[u8; std::mem::size_of::<u16>()]>,
-    endianness: Endianness,
-}
+pub struct Uint16();
 
 impl Uint16 {
     // type DataType = u16;
 
+    const NAME: &'static str = "u16";
     const PACKED_SIZE: usize = std::mem::size_of::<u16>();
+    const ALIGNMENT: usize = std::mem::align_of::<u16>();
 
     fn to_bytes(endianness: &Endianness, value: u16) -> [u8; Self::PACKED_SIZE] {
         match endianness {
-            Endianness::Native(_) => value.to_ne_bytes(),
-            Endianness::Big(_) => value.to_be_bytes(),
-            Endianness::Little(_) => value.to_le_bytes(),
+            Endianness::Native => value.to_ne_bytes(),
+            Endianness::Big => value.to_be_bytes(),
+            Endianness::Little => value.to_le_bytes(),
         }
     }
 
-    fn from_bytes(endianness: &Endianness, buffer: &RefCell<[u8; Self::PACKED_SIZE]>) -> u16 {
+    fn from_bytes(endianness: &Endianness, buffer: &[u8]) -> u16 {
+        let buffer = *buffer_alias::<{ Self::PACKED_SIZE }>(buffer);
         match endianness {
-            Endianness::Native(_) => u16::from_ne_bytes(*buffer.borrow()),
-            Endianness::Big(_) => u16::from_be_bytes(*buffer.borrow()),
-            Endianness::Little(_) => u16::from_le_bytes(*buffer.borrow()),
+            Endianness::Native => u16::from_ne_bytes(buffer),
+            Endianness::Big => u16::from_be_bytes(buffer),
+            Endianness::Little => u16::from_le_bytes(buffer),
+        }
+    }
+
+    /// Converts a Python int, handling values which don't fit according to `overflow`
+    fn convert(value: &PyAny, overflow: Overflow) -> PyResult<u16> {
+        let err = match value.extract::<u16>() {
+            Ok(literal) => return Ok(literal),
+            Err(err) => err,
+        };
+        if !err.is_instance_of::<PyOverflowError>(value.py()) {
+            return Err(err);
+        }
+
+        match overflow {
+            Overflow::Error => Err(range_error(value, Self::NAME, u16::MIN, u16::MAX)),
+            Overflow::Wrap => {
+                // Masking in Python keeps the bits a two's complement representation of the value would have
+                let mask = u128::MAX >> (128 - 8 * Self::PACKED_SIZE);
+                let bits = value
+                    .call_method0("__index__")?
+                    .call_method1("__and__", (mask,))?
+                    .extract::<u128>()?;
+                Ok(bits as u16)
+            }
+            Overflow::Saturate => match value.lt(0)? {
+                true => Ok(u16::MIN),
+                false => Ok(u16::MAX),
+            },
         }
     }
 
-    fn value(&self) -> u16 {
-        Self::from_bytes(&self.endianness, &self.buffer)
+    fn value(py: Python<'_>, shaped: &PyShaped) -> u16 {
+        shaped
+            .buffer
+            .read(py, |buffer| Self::from_bytes(&shaped.endianness, buffer))
     }
 }
 
 #[pymethods]
 impl Uint16 {
     #[new]
-    fn new(value: Option<&PyAny>, endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        match value {
+    #[pyo3(signature = (value = None, endianness = None, overflow = None))]
+    fn new(
+        py: Python<'_>,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+        overflow: Option<Overflow>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or(Endianness::Native);
+        let buffer = match value {
             Some(value) => {
-                if let Ok(literal) = value.downcast::<PyLong>() {
-                    let literal = literal.extract::<u16>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, literal)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
-                } else if let Ok(buffer) = value.downcast::<PyBytes>() {
-                    let buffer = buffer.extract::<&[u8]>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(*buffer_alias::<{ Self::PACKED_SIZE }>(buffer)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                if value.hasattr("__index__")? {
+                    let literal = Self::convert(value, overflow.unwrap_or_default())?;
+                    Self::to_bytes(&endianness, literal)
+                } else if let Some(buffer) = bytes_of(value)? {
+                    packed_buffer::<{ Self::PACKED_SIZE }>(Self::NAME, &buffer)?
                 } else {
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, u16::default())),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                    return Err(unsupported_value(Self::NAME, value)?);
                 }
             }
-            None => (
-                Self {
-                    buffer: RefCell::new(Self::to_bytes(&endianness, u16::default())),
-                    endianness,
-                },
-                PyShaped::new(),
-            ),
-        }
+            None => Self::to_bytes(&endianness, u16::default()),
+        };
+        Ok((
+            Self(),
+            PyShaped::with_buffer(py, buffer.to_vec(), endianness)?,
+        ))
     }
 
     fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
         let class_name: &str = slf.get_type().name()?;
-        let value = slf.borrow().value();
+        let value = Self::value(slf.py(), slf.borrow().as_ref());
         Ok(format!("{class_name}({})", value))
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+    fn __richcmp__(slf: PyRef<'_, Self>, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let py = slf.py();
+        let (value, other) = (
+            Self::value(py, slf.as_ref()),
+            Self::value(py, other.as_ref()),
+        );
         match op {
-            CompareOp::Lt => Ok(self.value() < other.value()),
-            CompareOp::Le => Ok(self.value() <= other.value()),
-            CompareOp::Eq => Ok(self.value() == other.value()),
-            CompareOp::Ne => Ok(self.value() != other.value()),
-            CompareOp::Gt => Ok(self.value() > other.value()),
-            CompareOp::Ge => Ok(self.value() >= other.value()),
+            CompareOp::Lt => Ok(value < other),
+            CompareOp::Le => Ok(value <= other),
+            CompareOp::Eq => Ok(value == other),
+            CompareOp::Ne => Ok(value != other),
+            CompareOp::Gt => Ok(value > other),
+            CompareOp::Ge => Ok(value >= other),
         }
     }
 
+    fn __index__(slf: PyRef<'_, Self>) -> u16 {
+        Self::value(slf.py(), slf.as_ref())
+    }
+
     #[classmethod]
     fn __packed_size__(_cls: &PyType) -> PyResult<usize> {
         Ok(Self::PACKED_SIZE)
     }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(Self::ALIGNMENT)
+    }
+
+    /// Returns the equivalent NumPy dtype, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let kind = 'u';
+        scalar_dtype(cls, kind, Self::PACKED_SIZE, endianness.unwrap_or_default())
+    }
 }
 
 #[pyclass(module = "_cerialize", name = "u32", subclass, weakref, extends=PyShaped)]
-pub struct Uint32 {
-    buffer: RefCell<[u8; std::mem::size_of::<u32>()]>,
-    endianness: Endianness,
-}
+pub struct Uint32();
 
 impl Uint32 {
     // type DataType = u32;
 
+    const NAME: &'static str = "u32";
     const PACKED_SIZE: usize = std::mem::size_of::<u32>();
+    const ALIGNMENT: usize = std::mem::align_of::<u32>();
 
     fn to_bytes(endianness: &Endianness, value: u32) -> [u8; Self::PACKED_SIZE] {
         match endianness {
-            Endianness::Native(_) => value.to_ne_bytes(),
-            Endianness::Big(_) => value.to_be_bytes(),
-            Endianness::Little(_) => value.to_le_bytes(),
+            Endianness::Native => value.to_ne_bytes(),
+            Endianness::Big => value.to_be_bytes(),
+            Endianness::Little => value.to_le_bytes(),
         }
     }
 
-    fn from_bytes(endianness: &Endianness, buffer: &RefCell<[u8; Self::PACKED_SIZE]>) -> u32 {
+    fn from_bytes(endianness: &Endianness, buffer: &[u8]) -> u32 {
+        let buffer = *buffer_alias::<{ Self::PACKED_SIZE }>(buffer);
         match endianness {
-            Endianness::Native(_) => u32::from_ne_bytes(*buffer.borrow()),
-            Endianness::Big(_) => u32::from_be_bytes(*buffer.borrow()),
-            Endianness::Little(_) => u32::from_le_bytes(*buffer.borrow()),
+            Endianness::Native => u32::from_ne_bytes(buffer),
+            Endianness::Big => u32::from_be_bytes(buffer),
+            Endianness::Little => u32::from_le_bytes(buffer),
         }
     }
 
-    fn value(&self) -> u32 {
-        Self::from_bytes(&self.endianness, &self.buffer)
+    /// Converts a Python int, handling values which don't fit according to `overflow`
+    fn convert(value: &PyAny, overflow: Overflow) -> PyResult<u32> {
+        let err = match value.extract::<u32>() {
+            Ok(literal) => return Ok(literal),
+            Err(err) => err,
+        };
+        if !err.is_instance_of::<PyOverflowError>(value.py()) {
+            return Err(err);
+        }
+
+        match overflow {
+            Overflow::Error => Err(range_error(value, Self::NAME, u32::MIN, u32::MAX)),
+            Overflow::Wrap => {
+                // Masking in Python keeps the bits a two's complement representation of the value would have
+                let mask = u128::MAX >> (128 - 8 * Self::PACKED_SIZE);
+                let bits = value
+                    .call_method0("__index__")?
+                    .call_method1("__and__", (mask,))?
+                    .extract::<u128>()?;
+                Ok(bits as u32)
+            }
+            Overflow::Saturate => match value.lt(0)? {
+                true => Ok(u32::MIN),
+                false => Ok(u32::MAX),
+            },
+        }
+    }
+
+    fn value(py: Python<'_>, shaped: &PyShaped) -> u32 {
+        shaped
+            .buffer
+            .read(py, |buffer| Self::from_bytes(&shaped.endianness, buffer))
     }
 }
 
 #[pymethods]
 impl Uint32 {
     #[new]
-    fn new(value: Option<&PyAny>, endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        match value {
+    #[pyo3(signature = (value = None, endianness = None, overflow = None))]
+    fn new(
+        py: Python<'_>,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+        overflow: Option<Overflow>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or(Endianness::Native);
+        let buffer = match value {
             Some(value) => {
-                if let Ok(literal) = value.downcast::<PyLong>() {
-                    let literal = literal.extract::<u32>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, literal)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
-                } else if let Ok(buffer) = value.downcast::<PyBytes>() {
-                    let buffer = buffer.extract::<&[u8]>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(*buffer_alias::<{ Self::PACKED_SIZE }>(buffer)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                if value.hasattr("__index__")? {
+                    let literal = Self::convert(value, overflow.unwrap_or_default())?;
+                    Self::to_bytes(&endianness, literal)
+                } else if let Some(buffer) = bytes_of(value)? {
+                    packed_buffer::<{ Self::PACKED_SIZE }>(Self::NAME, &buffer)?
                 } else {
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, u32::default())),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                    return Err(unsupported_value(Self::NAME, value)?);
                 }
             }
-            None => (
-                Self {
-                    buffer: RefCell::new(Self::to_bytes(&endianness, u32::default())),
-                    endianness,
-                },
-                PyShaped::new(),
-            ),
-        }
+            None => Self::to_bytes(&endianness, u32::default()),
+        };
+        Ok((
+            Self(),
+            PyShaped::with_buffer(py, buffer.to_vec(), endianness)?,
+        ))
     }
 
     fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
         let class_name: &str = slf.get_type().name()?;
-        let value = slf.borrow().value();
+        let value = Self::value(slf.py(), slf.borrow().as_ref());
         Ok(format!("{class_name}({})", value))
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+    fn __richcmp__(slf: PyRef<'_, Self>, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let py = slf.py();
+        let (value, other) = (
+            Self::value(py, slf.as_ref()),
+            Self::value(py, other.as_ref()),
+        );
         match op {
-            CompareOp::Lt => Ok(self.value() < other.value()),
-            CompareOp::Le => Ok(self.value() <= other.value()),
-            CompareOp::Eq => Ok(self.value() == other.value()),
-            CompareOp::Ne => Ok(self.value() != other.value()),
-            CompareOp::Gt => Ok(self.value() > other.value()),
-            CompareOp::Ge => Ok(self.value() >= other.value()),
+            CompareOp::Lt => Ok(value < other),
+            CompareOp::Le => Ok(value <= other),
+            CompareOp::Eq => Ok(value == other),
+            CompareOp::Ne => Ok(value != other),
+            CompareOp::Gt => Ok(value > other),
+            CompareOp::Ge => Ok(value >= other),
         }
     }
 
+    fn __index__(slf: PyRef<'_, Self>) -> u32 {
+        Self::value(slf.py(), slf.as_ref())
+    }
+
     #[classmethod]
     fn __packed_size__(_cls: &PyType) -> PyResult<usize> {
         Ok(Self::PACKED_SIZE)
     }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(Self::ALIGNMENT)
+    }
+
+    /// Returns the equivalent NumPy dtype, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let kind = 'u';
+        scalar_dtype(cls, kind, Self::PACKED_SIZE, endianness.unwrap_or_default())
+    }
 }
 
 #[pyclass(module = "_cerialize", name = "u64", subclass, weakref, extends=PyShaped)]
-pub struct Uint64 {
-    buffer: RefCell<[u8; std::mem::size_of::<u64>()]>,
-    endianness: Endianness,
-}
+pub struct Uint64();
 
 impl Uint64 {
     // type DataType = u64;
 
+    const NAME: &'static str = "u64";
     const PACKED_SIZE: usize = std::mem::size_of::<u64>();
+    const ALIGNMENT: usize = std::mem::align_of::<u64>();
 
     fn to_bytes(endianness: &Endianness, value: u64) -> [u8; Self::PACKED_SIZE] {
         match endianness {
-            Endianness::Native(_) => value.to_ne_bytes(),
-            Endianness::Big(_) => value.to_be_bytes(),
-            Endianness::Little(_) => value.to_le_bytes(),
+            Endianness::Native => value.to_ne_bytes(),
+            Endianness::Big => value.to_be_bytes(),
+            Endianness::Little => value.to_le_bytes(),
         }
     }
 
-    fn from_bytes(endianness: &Endianness, buffer: &RefCell<[u8; Self::PACKED_SIZE]>) -> u64 {
+    fn from_bytes(endianness: &Endianness, buffer: &[u8]) -> u64 {
+        let buffer = *buffer_alias::<{ Self::PACKED_SIZE }>(buffer);
         match endianness {
-            Endianness::Native(_) => u64::from_ne_bytes(*buffer.borrow()),
-            Endianness::Big(_) => u64::from_be_bytes(*buffer.borrow()),
-            Endianness::Little(_) => u64::from_le_bytes(*buffer.borrow()),
+            Endianness::Native => u64::from_ne_bytes(buffer),
+            Endianness::Big => u64::from_be_bytes(buffer),
+            Endianness::Little => u64::from_le_bytes(buffer),
         }
     }
 
-    fn value(&self) -> u64 {
-        Self::from_bytes(&self.endianness, &self.buffer)
+    /// Converts a Python int, handling values which don't fit according to `overflow`
+    fn convert(value: &PyAny, overflow: Overflow) -> PyResult<u64> {
+        let err = match value.extract::<u64>() {
+            Ok(literal) => return Ok(literal),
+            Err(err) => err,
+        };
+        if !err.is_instance_of::<PyOverflowError>(value.py()) {
+            return Err(err);
+        }
+
+        match overflow {
+            Overflow::Error => Err(range_error(value, Self::NAME, u64::MIN, u64::MAX)),
+            Overflow::Wrap => {
+                // Masking in Python keeps the bits a two's complement representation of the value would have
+                let mask = u128::MAX >> (128 - 8 * Self::PACKED_SIZE);
+                let bits = value
+                    .call_method0("__index__")?
+                    .call_method1("__and__", (mask,))?
+                    .extract::<u128>()?;
+                Ok(bits as u64)
+            }
+            Overflow::Saturate => match value.lt(0)? {
+                true => Ok(u64::MIN),
+                false => Ok(u64::MAX),
+            },
+        }
+    }
+
+    fn value(py: Python<'_>, shaped: &PyShaped) -> u64 {
+        shaped
+            .buffer
+            .read(py, |buffer| Self::from_bytes(&shaped.endianness, buffer))
     }
 }
 
 #[pymethods]
 impl Uint64 {
     #[new]
-    fn new(value: Option<&PyAny>, endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        match value {
+    #[pyo3(signature = (value = None, endianness = None, overflow = None))]
+    fn new(
+        py: Python<'_>,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+        overflow: Option<Overflow>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or(Endianness::Native);
+        let buffer = match value {
             Some(value) => {
-                if let Ok(literal) = value.downcast::<PyLong>() {
-                    let literal = literal.extract::<u64>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, literal)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
-                } else if let Ok(buffer) = value.downcast::<PyBytes>() {
-                    let buffer = buffer.extract::<&[u8]>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(*buffer_alias::<{ Self::PACKED_SIZE }>(buffer)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                if value.hasattr("__index__")? {
+                    let literal = Self::convert(value, overflow.unwrap_or_default())?;
+                    Self::to_bytes(&endianness, literal)
+                } else if let Some(buffer) = bytes_of(value)? {
+                    packed_buffer::<{ Self::PACKED_SIZE }>(Self::NAME, &buffer)?
                 } else {
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, u64::default())),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                    return Err(unsupported_value(Self::NAME, value)?);
                 }
             }
-            None => (
-                Self {
-                    buffer: RefCell::new(Self::to_bytes(&endianness, u64::default())),
-                    endianness,
-                },
-                PyShaped::new(),
-            ),
-        }
+            None => Self::to_bytes(&endianness, u64::default()),
+        };
+        Ok((
+            Self(),
+            PyShaped::with_buffer(py, buffer.to_vec(), endianness)?,
+        ))
     }
 
     fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
         let class_name: &str = slf.get_type().name()?;
-        let value = slf.borrow().value();
+        let value = Self::value(slf.py(), slf.borrow().as_ref());
         Ok(format!("{class_name}({})", value))
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+    fn __richcmp__(slf: PyRef<'_, Self>, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let py = slf.py();
+        let (value, other) = (
+            Self::value(py, slf.as_ref()),
+            Self::value(py, other.as_ref()),
+        );
         match op {
-            CompareOp::Lt => Ok(self.value() < other.value()),
-            CompareOp::Le => Ok(self.value() <= other.value()),
-            CompareOp::Eq => Ok(self.value() == other.value()),
-            CompareOp::Ne => Ok(self.value() != other.value()),
-            CompareOp::Gt => Ok(self.value() > other.value()),
-            CompareOp::Ge => Ok(self.value() >= other.value()),
+            CompareOp::Lt => Ok(value < other),
+            CompareOp::Le => Ok(value <= other),
+            CompareOp::Eq => Ok(value == other),
+            CompareOp::Ne => Ok(value != other),
+            CompareOp::Gt => Ok(value > other),
+            CompareOp::Ge => Ok(value >= other),
         }
     }
 
+    fn __index__(slf: PyRef<'_, Self>) -> u64 {
+        Self::value(slf.py(), slf.as_ref())
+    }
+
     #[classmethod]
     fn __packed_size__(_cls: &PyType) -> PyResult<usize> {
         Ok(Self::PACKED_SIZE)
     }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(Self::ALIGNMENT)
+    }
+
+    /// Returns the equivalent NumPy dtype, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let kind = 'u';
+        scalar_dtype(cls, kind, Self::PACKED_SIZE, endianness.unwrap_or_default())
+    }
 }
 
 #[pyclass(module = "_cerialize", name = "u128", subclass, weakref, extends=PyShaped)]
-pub struct Uint128 {
-    buffer: RefCell<[u8; std::mem::size_of::<u128>()]>,
-    endianness: Endianness,
-}
+pub struct Uint128();
 
 impl Uint128 {
     // type DataType = u128;
 
+    const NAME: &'static str = "u128";
     const PACKED_SIZE: usize = std::mem::size_of::<u128>();
+    const ALIGNMENT: usize = std::mem::align_of::<u128>();
 
     fn to_bytes(endianness: &Endianness, value: u128) -> [u8; Self::PACKED_SIZE] {
         match endianness {
-            Endianness::Native(_) => value.to_ne_bytes(),
-            Endianness::Big(_) => value.to_be_bytes(),
-            Endianness::Little(_) => value.to_le_bytes(),
+            Endianness::Native => value.to_ne_bytes(),
+            Endianness::Big => value.to_be_bytes(),
+            Endianness::Little => value.to_le_bytes(),
         }
     }
 
-    fn from_bytes(endianness: &Endianness, buffer: &RefCell<[u8; Self::PACKED_SIZE]>) -> u128 {
+    fn from_bytes(endianness: &Endianness, buffer: &[u8]) -> u128 {
+        let buffer = *buffer_alias::<{ Self::PACKED_SIZE }>(buffer);
         match endianness {
-            Endianness::Native(_) => u128::from_ne_bytes(*buffer.borrow()),
-            Endianness::Big(_) => u128::from_be_bytes(*buffer.borrow()),
-            Endianness::Little(_) => u128::from_le_bytes(*buffer.borrow()),
+            Endianness::Native => u128::from_ne_bytes(buffer),
+            Endianness::Big => u128::from_be_bytes(buffer),
+            Endianness::Little => u128::from_le_bytes(buffer),
         }
     }
 
-    fn value(&self) -> u128 {
-        Self::from_bytes(&self.endianness, &self.buffer)
+    /// Converts a Python int, handling values which don't fit according to `overflow`
+    fn convert(value: &PyAny, overflow: Overflow) -> PyResult<u128> {
+        let err = match value.extract::<u128>() {
+            Ok(literal) => return Ok(literal),
+            Err(err) => err,
+        };
+        if !err.is_instance_of::<PyOverflowError>(value.py()) {
+            return Err(err);
+        }
+
+        match overflow {
+            Overflow::Error => Err(range_error(value, Self::NAME, u128::MIN, u128::MAX)),
+            Overflow::Wrap => {
+                // Masking in Python keeps the bits a two's complement representation of the value would have
+                let mask = u128::MAX >> (128 - 8 * Self::PACKED_SIZE);
+                let bits = value
+                    .call_method0("__index__")?
+                    .call_method1("__and__", (mask,))?
+                    .extract::<u128>()?;
+                Ok(bits)
+            }
+            Overflow::Saturate => match value.lt(0)? {
+                true => Ok(u128::MIN),
+                false => Ok(u128::MAX),
+            },
+        }
+    }
+
+    fn value(py: Python<'_>, shaped: &PyShaped) -> u128 {
+        shaped
+            .buffer
+            .read(py, |buffer| Self::from_bytes(&shaped.endianness, buffer))
     }
 }
 
 #[pymethods]
 impl Uint128 {
     #[new]
-    fn new(value: Option<&PyAny>, endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        match value {
+    #[pyo3(signature = (value = None, endianness = None, overflow = None))]
+    fn new(
+        py: Python<'_>,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+        overflow: Option<Overflow>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or(Endianness::Native);
+        let buffer = match value {
             Some(value) => {
-                if let Ok(literal) = value.downcast::<PyLong>() {
-                    let literal = literal.extract::<u128>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, literal)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
-                } else if let Ok(buffer) = value.downcast::<PyBytes>() {
-                    let buffer = buffer.extract::<&[u8]>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(*buffer_alias::<{ Self::PACKED_SIZE }>(buffer)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                if value.hasattr("__index__")? {
+                    let literal = Self::convert(value, overflow.unwrap_or_default())?;
+                    Self::to_bytes(&endianness, literal)
+                } else if let Some(buffer) = bytes_of(value)? {
+                    packed_buffer::<{ Self::PACKED_SIZE }>(Self::NAME, &buffer)?
                 } else {
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, u128::default())),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                    return Err(unsupported_value(Self::NAME, value)?);
                 }
             }
-            None => (
-                Self {
-                    buffer: RefCell::new(Self::to_bytes(&endianness, u128::default())),
-                    endianness,
-                },
-                PyShaped::new(),
-            ),
-        }
+            None => Self::to_bytes(&endianness, u128::default()),
+        };
+        Ok((
+            Self(),
+            PyShaped::with_buffer(py, buffer.to_vec(), endianness)?,
+        ))
     }
 
     fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
         let class_name: &str = slf.get_type().name()?;
-        let value = slf.borrow().value();
+        let value = Self::value(slf.py(), slf.borrow().as_ref());
         Ok(format!("{class_name}({})", value))
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+    fn __richcmp__(slf: PyRef<'_, Self>, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let py = slf.py();
+        let (value, other) = (
+            Self::value(py, slf.as_ref()),
+            Self::value(py, other.as_ref()),
+        );
         match op {
-            CompareOp::Lt => Ok(self.value() < other.value()),
-            CompareOp::Le => Ok(self.value() <= other.value()),
-            CompareOp::Eq => Ok(self.value() == other.value()),
-            CompareOp::Ne => Ok(self.value() != other.value()),
-            CompareOp::Gt => Ok(self.value() > other.value()),
-            CompareOp::Ge => Ok(self.value() >= other.value()),
+            CompareOp::Lt => Ok(value < other),
+            CompareOp::Le => Ok(value <= other),
+            CompareOp::Eq => Ok(value == other),
+            CompareOp::Ne => Ok(value != other),
+            CompareOp::Gt => Ok(value > other),
+            CompareOp::Ge => Ok(value >= other),
         }
     }
 
+    fn __index__(slf: PyRef<'_, Self>) -> u128 {
+        Self::value(slf.py(), slf.as_ref())
+    }
+
     #[classmethod]
     fn __packed_size__(_cls: &PyType) -> PyResult<usize> {
         Ok(Self::PACKED_SIZE)
     }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(Self::ALIGNMENT)
+    }
+
+    /// Returns the equivalent NumPy dtype, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let kind = 'u';
+        scalar_dtype(cls, kind, Self::PACKED_SIZE, endianness.unwrap_or_default())
+    }
 }
 
 #[pyclass(module = "_cerialize", name = "f16", subclass, weakref, extends=PyShaped)]
-pub struct Float16 {
-    buffer: RefCell<[u8; std::mem::size_of::<f16>()]>,
-    endianness: Endianness,
-}
+pub struct Float16();
 
 impl Float16 {
     // type DataType = f16;
 
+    const NAME: &'static str = "f16";
     const PACKED_SIZE: usize = std::mem::size_of::<f16>();
+    const ALIGNMENT: usize = std::mem::align_of::<f16>();
 
     fn to_bytes(endianness: &Endianness, value: f16) -> [u8; Self::PACKED_SIZE] {
         match endianness {
-            Endianness::Native(_) => value.to_ne_bytes(),
-            Endianness::Big(_) => value.to_be_bytes(),
-            Endianness::Little(_) => value.to_le_bytes(),
+            Endianness::Native => value.to_ne_bytes(),
+            Endianness::Big => value.to_be_bytes(),
+            Endianness::Little => value.to_le_bytes(),
         }
     }
 
-    fn from_bytes(endianness: &Endianness, buffer: &RefCell<[u8; Self::PACKED_SIZE]>) -> f16 {
+    fn from_bytes(endianness: &Endianness, buffer: &[u8]) -> f16 {
+        let buffer = *buffer_alias::<{ Self::PACKED_SIZE }>(buffer);
         match endianness {
-            Endianness::Native(_) => f16::from_ne_bytes(*buffer.borrow()),
-            Endianness::Big(_) => f16::from_be_bytes(*buffer.borrow()),
-            Endianness::Little(_) => f16::from_le_bytes(*buffer.borrow()),
+            Endianness::Native => f16::from_ne_bytes(buffer),
+            Endianness::Big => f16::from_be_bytes(buffer),
+            Endianness::Little => f16::from_le_bytes(buffer),
         }
     }
 
-    fn value(&self) -> f16 {
-        Self::from_bytes(&self.endianness, &self.buffer)
+    fn value(py: Python<'_>, shaped: &PyShaped) -> f16 {
+        shaped
+            .buffer
+            .read(py, |buffer| Self::from_bytes(&shaped.endianness, buffer))
     }
 }
 
 #[pymethods]
 impl Float16 {
     #[new]
-    fn new(value: Option<&PyAny>, endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        match value {
+    fn new(
+        py: Python<'_>,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or(Endianness::Native);
+        let buffer = match value {
             Some(value) => {
-                if let Ok(literal) = value.downcast::<PyLong>() {
-                    let literal = f16::from_f32(literal.extract::<f32>().unwrap());
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, literal)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
-                } else if let Ok(buffer) = value.downcast::<PyBytes>() {
-                    let buffer = buffer.extract::<&[u8]>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(*buffer_alias::<{ Self::PACKED_SIZE }>(buffer)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                if value.hasattr("__float__")? {
+                    let literal = f16::from_f64(value.extract::<f64>()?);
+                    Self::to_bytes(&endianness, literal)
+                } else if let Some(buffer) = bytes_of(value)? {
+                    packed_buffer::<{ Self::PACKED_SIZE }>(Self::NAME, &buffer)?
                 } else {
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, f16::default())),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                    return Err(unsupported_value(Self::NAME, value)?);
                 }
             }
-            None => (
-                Self {
-                    buffer: RefCell::new(Self::to_bytes(&endianness, f16::default())),
-                    endianness,
-                },
-                PyShaped::new(),
-            ),
-        }
+            None => Self::to_bytes(&endianness, f16::default()),
+        };
+        Ok((
+            Self(),
+            PyShaped::with_buffer(py, buffer.to_vec(), endianness)?,
+        ))
     }
 
     fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
         let class_name: &str = slf.get_type().name()?;
-        let value = slf.borrow().value();
+        let value = Self::value(slf.py(), slf.borrow().as_ref());
         Ok(format!("{class_name}({})", value))
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+    fn __richcmp__(slf: PyRef<'_, Self>, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let py = slf.py();
+        let (value, other) = (
+            Self::value(py, slf.as_ref()),
+            Self::value(py, other.as_ref()),
+        );
         match op {
-            CompareOp::Lt => Ok(self.value() < other.value()),
-            CompareOp::Le => Ok(self.value() <= other.value()),
-            CompareOp::Eq => Ok(self.value() == other.value()),
-            CompareOp::Ne => Ok(self.value() != other.value()),
-            CompareOp::Gt => Ok(self.value() > other.value()),
-            CompareOp::Ge => Ok(self.value() >= other.value()),
+            CompareOp::Lt => Ok(value < other),
+            CompareOp::Le => Ok(value <= other),
+            CompareOp::Eq => Ok(value == other),
+            CompareOp::Ne => Ok(value != other),
+            CompareOp::Gt => Ok(value > other),
+            CompareOp::Ge => Ok(value >= other),
         }
     }
 
+    fn __float__(slf: PyRef<'_, Self>) -> f64 {
+        Self::value(slf.py(), slf.as_ref()).to_f64()
+    }
+
     #[classmethod]
     fn __packed_size__(_cls: &PyType) -> PyResult<usize> {
         Ok(Self::PACKED_SIZE)
     }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(Self::ALIGNMENT)
+    }
+
+    /// Returns the equivalent NumPy dtype, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let kind = 'f';
+        scalar_dtype(cls, kind, Self::PACKED_SIZE, endianness.unwrap_or_default())
+    }
 }
 
 #[pyclass(module = "_cerialize", name = "f32", subclass, weakref, extends=PyShaped)]
-pub struct Float32 {
-    buffer: RefCell<[u8; std::mem::size_of::<f32>()]>,
-    endianness: Endianness,
-}
+pub struct Float32();
 
 impl Float32 {
     // type DataType = f32;
 
+    const NAME: &'static str = "f32";
     const PACKED_SIZE: usize = std::mem::size_of::<f32>();
+    const ALIGNMENT: usize = std::mem::align_of::<f32>();
 
     fn to_bytes(endianness: &Endianness, value: f32) -> [u8; Self::PACKED_SIZE] {
         match endianness {
-            Endianness::Native(_) => value.to_ne_bytes(),
-            Endianness::Big(_) => value.to_be_bytes(),
-            Endianness::Little(_) => value.to_le_bytes(),
+            Endianness::Native => value.to_ne_bytes(),
+            Endianness::Big => value.to_be_bytes(),
+            Endianness::Little => value.to_le_bytes(),
         }
     }
 
-    fn from_bytes(endianness: &Endianness, buffer: &RefCell<[u8; Self::PACKED_SIZE]>) -> f32 {
+    fn from_bytes(endianness: &Endianness, buffer: &[u8]) -> f32 {
+        let buffer = *buffer_alias::<{ Self::PACKED_SIZE }>(buffer);
         match endianness {
-            Endianness::Native(_) => f32::from_ne_bytes(*buffer.borrow()),
-            Endianness::Big(_) => f32::from_be_bytes(*buffer.borrow()),
-            Endianness::Little(_) => f32::from_le_bytes(*buffer.borrow()),
+            Endianness::Native => f32::from_ne_bytes(buffer),
+            Endianness::Big => f32::from_be_bytes(buffer),
+            Endianness::Little => f32::from_le_bytes(buffer),
         }
     }
 
-    fn value(&self) -> f32 {
-        Self::from_bytes(&self.endianness, &self.buffer)
+    fn value(py: Python<'_>, shaped: &PyShaped) -> f32 {
+        shaped
+            .buffer
+            .read(py, |buffer| Self::from_bytes(&shaped.endianness, buffer))
     }
 }
 
 #[pymethods]
 impl Float32 {
     #[new]
-    fn new(value: Option<&PyAny>, endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        match value {
+    fn new(
+        py: Python<'_>,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or(Endianness::Native);
+        let buffer = match value {
             Some(value) => {
-                if let Ok(literal) = value.downcast::<PyLong>() {
-                    let literal = literal.extract::<f32>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, literal)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
-                } else if let Ok(buffer) = value.downcast::<PyBytes>() {
-                    let buffer = buffer.extract::<&[u8]>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(*buffer_alias::<{ Self::PACKED_SIZE }>(buffer)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                if value.hasattr("__float__")? {
+                    let literal = value.extract::<f32>()?;
+                    Self::to_bytes(&endianness, literal)
+                } else if let Some(buffer) = bytes_of(value)? {
+                    packed_buffer::<{ Self::PACKED_SIZE }>(Self::NAME, &buffer)?
                 } else {
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, f32::default())),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                    return Err(unsupported_value(Self::NAME, value)?);
                 }
             }
-            None => (
-                Self {
-                    buffer: RefCell::new(Self::to_bytes(&endianness, f32::default())),
-                    endianness,
-                },
-                PyShaped::new(),
-            ),
-        }
+            None => Self::to_bytes(&endianness, f32::default()),
+        };
+        Ok((
+            Self(),
+            PyShaped::with_buffer(py, buffer.to_vec(), endianness)?,
+        ))
     }
 
     fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
         let class_name: &str = slf.get_type().name()?;
-        let value = slf.borrow().value();
+        let value = Self::value(slf.py(), slf.borrow().as_ref());
         Ok(format!("{class_name}({})", value))
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+    fn __richcmp__(slf: PyRef<'_, Self>, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let py = slf.py();
+        let (value, other) = (
+            Self::value(py, slf.as_ref()),
+            Self::value(py, other.as_ref()),
+        );
         match op {
-            CompareOp::Lt => Ok(self.value() < other.value()),
-            CompareOp::Le => Ok(self.value() <= other.value()),
-            CompareOp::Eq => Ok(self.value() == other.value()),
-            CompareOp::Ne => Ok(self.value() != other.value()),
-            CompareOp::Gt => Ok(self.value() > other.value()),
-            CompareOp::Ge => Ok(self.value() >= other.value()),
+            CompareOp::Lt => Ok(value < other),
+            CompareOp::Le => Ok(value <= other),
+            CompareOp::Eq => Ok(value == other),
+            CompareOp::Ne => Ok(value != other),
+            CompareOp::Gt => Ok(value > other),
+            CompareOp::Ge => Ok(value >= other),
         }
     }
 
+    fn __float__(slf: PyRef<'_, Self>) -> f64 {
+        Self::value(slf.py(), slf.as_ref()) as f64
+    }
+
     #[classmethod]
     fn __packed_size__(_cls: &PyType) -> PyResult<usize> {
         Ok(Self::PACKED_SIZE)
     }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(Self::ALIGNMENT)
+    }
+
+    /// Returns the equivalent NumPy dtype, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let kind = 'f';
+        scalar_dtype(cls, kind, Self::PACKED_SIZE, endianness.unwrap_or_default())
+    }
 }
 
 #[pyclass(module = "_cerialize", name = "f64", subclass, weakref, extends=PyShaped)]
-pub struct Float64 {
-    buffer: RefCell<[u8; std::mem::size_of::<f64>()]>,
-    endianness: Endianness,
-}
+pub struct Float64();
 
 impl Float64 {
     // type DataType = f64;
 
+    const NAME: &'static str = "f64";
     const PACKED_SIZE: usize = std::mem::size_of::<f64>();
+    const ALIGNMENT: usize = std::mem::align_of::<f64>();
 
     fn to_bytes(endianness: &Endianness, value: f64) -> [u8; Self::PACKED_SIZE] {
         match endianness {
-            Endianness::Native(_) => value.to_ne_bytes(),
-            Endianness::Big(_) => value.to_be_bytes(),
-            Endianness::Little(_) => value.to_le_bytes(),
+            Endianness::Native => value.to_ne_bytes(),
+            Endianness::Big => value.to_be_bytes(),
+            Endianness::Little => value.to_le_bytes(),
         }
     }
 
-    fn from_bytes(endianness: &Endianness, buffer: &RefCell<[u8; Self::PACKED_SIZE]>) -> f64 {
+    fn from_bytes(endianness: &Endianness, buffer: &[u8]) -> f64 {
+        let buffer = *buffer_alias::<{ Self::PACKED_SIZE }>(buffer);
         match endianness {
-            Endianness::Native(_) => f64::from_ne_bytes(*buffer.borrow()),
-            Endianness::Big(_) => f64::from_be_bytes(*buffer.borrow()),
-            Endianness::Little(_) => f64::from_le_bytes(*buffer.borrow()),
+            Endianness::Native => f64::from_ne_bytes(buffer),
+            Endianness::Big => f64::from_be_bytes(buffer),
+            Endianness::Little => f64::from_le_bytes(buffer),
         }
     }
 
-    fn value(&self) -> f64 {
-        Self::from_bytes(&self.endianness, &self.buffer)
+    fn value(py: Python<'_>, shaped: &PyShaped) -> f64 {
+        shaped
+            .buffer
+            .read(py, |buffer| Self::from_bytes(&shaped.endianness, buffer))
     }
 }
 
 #[pymethods]
 impl Float64 {
     #[new]
-    fn new(value: Option<&PyAny>, endianness: Option<Endianness>) -> (Self, PyShaped) {
-        let endianness = endianness.unwrap_or(Endianness::Native(NativeEndian()));
-        match value {
+    fn new(
+        py: Python<'_>,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let endianness = endianness.unwrap_or(Endianness::Native);
+        let buffer = match value {
             Some(value) => {
-                if let Ok(literal) = value.downcast::<PyLong>() {
-                    let literal = literal.extract::<f64>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, literal)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
-                } else if let Ok(buffer) = value.downcast::<PyBytes>() {
-                    let buffer = buffer.extract::<&[u8]>().unwrap();
-                    (
-                        Self {
-                            buffer: RefCell::new(*buffer_alias::<{ Self::PACKED_SIZE }>(buffer)),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                if value.hasattr("__float__")? {
+                    let literal = value.extract::<f64>()?;
+                    Self::to_bytes(&endianness, literal)
+                } else if let Some(buffer) = bytes_of(value)? {
+                    packed_buffer::<{ Self::PACKED_SIZE }>(Self::NAME, &buffer)?
                 } else {
-                    (
-                        Self {
-                            buffer: RefCell::new(Self::to_bytes(&endianness, f64::default())),
-                            endianness,
-                        },
-                        PyShaped::new(),
-                    )
+                    return Err(unsupported_value(Self::NAME, value)?);
                 }
             }
-            None => (
-                Self {
-                    buffer: RefCell::new(Self::to_bytes(&endianness, f64::default())),
-                    endianness,
-                },
-                PyShaped::new(),
-            ),
-        }
+            None => Self::to_bytes(&endianness, f64::default()),
+        };
+        Ok((
+            Self(),
+            PyShaped::with_buffer(py, buffer.to_vec(), endianness)?,
+        ))
     }
 
     fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
         let class_name: &str = slf.get_type().name()?;
-        let value = slf.borrow().value();
+        let value = Self::value(slf.py(), slf.borrow().as_ref());
         Ok(format!("{class_name}({})", value))
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+    fn __richcmp__(slf: PyRef<'_, Self>, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let py = slf.py();
+        let (value, other) = (
+            Self::value(py, slf.as_ref()),
+            Self::value(py, other.as_ref()),
+        );
         match op {
-            CompareOp::Lt => Ok(self.value() < other.value()),
-            CompareOp::Le => Ok(self.value() <= other.value()),
-            CompareOp::Eq => Ok(self.value() == other.value()),
-            CompareOp::Ne => Ok(self.value() != other.value()),
-            CompareOp::Gt => Ok(self.value() > other.value()),
-            CompareOp::Ge => Ok(self.value() >= other.value()),
+            CompareOp::Lt => Ok(value < other),
+            CompareOp::Le => Ok(value <= other),
+            CompareOp::Eq => Ok(value == other),
+            CompareOp::Ne => Ok(value != other),
+            CompareOp::Gt => Ok(value > other),
+            CompareOp::Ge => Ok(value >= other),
         }
     }
 
+    fn __float__(slf: PyRef<'_, Self>) -> f64 {
+        Self::value(slf.py(), slf.as_ref())
+    }
+
     #[classmethod]
     fn __packed_size__(_cls: &PyType) -> PyResult<usize> {
         Ok(Self::PACKED_SIZE)
     }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(Self::ALIGNMENT)
+    }
+
+    /// Returns the equivalent NumPy dtype, in native byte order unless another one is given
+    #[classmethod]
+    #[pyo3(signature = (endianness = None))]
+    fn dtype(cls: &PyType, endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let kind = 'f';
+        scalar_dtype(cls, kind, Self::PACKED_SIZE, endianness.unwrap_or_default())
+    }
 }
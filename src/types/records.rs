@@ -0,0 +1,168 @@
+use super::{Endianness, PyShaped, View};
+use crate::errors::{BUFFER_SIZE_ERROR, LAYOUT_ERROR};
+use pyo3::exceptions::PyIndexError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyList, PySlice, PyType};
+
+/// A sequence of values of one type which are packed back to back in one buffer, as returned by `from_buffer_many`.
+/// The values are decoded when they are accessed and are views into the buffer rather than copies of it.
+#[pyclass(module = "_cerialize", name = "Records", sequence, frozen)]
+pub struct Records {
+    type_: Py<PyType>,
+    buffer: View,
+    size: usize,
+    len: usize,
+    endianness: Option<Endianness>,
+    /// The bytes after the last whole value
+    remainder: View,
+}
+
+impl Records {
+    pub fn new(
+        cls: &PyType,
+        buffer: View,
+        endianness: Option<Endianness>,
+        strict: bool,
+    ) -> PyResult<Self> {
+        let size = cls.call_method0("__packed_size__")?.extract::<usize>()?;
+        if size == 0 {
+            return Err(LAYOUT_ERROR.new_err(format!(
+                "{} has no packed size, so a buffer can't be split into its values",
+                cls.name()?
+            )));
+        }
+
+        let len = buffer.len() / size;
+        let trailing = buffer.len() % size;
+        if strict && trailing != 0 {
+            return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                "{} requires a buffer of a multiple of {size} bytes, got {} bytes ({trailing} trailing)",
+                cls.name()?,
+                buffer.len()
+            )));
+        }
+
+        Ok(Self {
+            type_: cls.into(),
+            remainder: buffer.slice(len * size, trailing),
+            buffer: buffer.slice(0, len * size),
+            size,
+            len,
+            endianness,
+        })
+    }
+
+    /// Returns a sequence of the `len` values starting at `position`
+    fn range(&self, position: usize, len: usize) -> Self {
+        Self {
+            type_: self.type_.clone(),
+            buffer: self.buffer.slice(position * self.size, len * self.size),
+            size: self.size,
+            len,
+            endianness: self.endianness,
+            remainder: self.remainder.slice(0, 0),
+        }
+    }
+
+    fn get<'py>(&self, py: Python<'py>, position: usize) -> PyResult<&'py PyAny> {
+        PyShaped::decode(
+            self.type_.clone_ref(py).into_ref(py),
+            self.buffer.slice(position * self.size, self.size),
+            self.endianness,
+        )
+    }
+}
+
+#[pymethods]
+impl Records {
+    /// The type of the values
+    #[getter]
+    fn r#type(&self, py: Python<'_>) -> Py<PyType> {
+        self.type_.clone_ref(py)
+    }
+
+    /// The trailing bytes which don't make up a whole value, which are always empty when decoding strictly
+    #[getter]
+    fn remainder<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        self.remainder.read(py, |bytes| PyBytes::new(py, bytes))
+    }
+
+    fn __len__(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the value at `index`, or the values selected by a slice which are a `Records` if the step is one and a list otherwise
+    fn __getitem__<'py>(&self, py: Python<'py>, index: &PyAny) -> PyResult<&'py PyAny> {
+        if let Ok(slice) = index.downcast::<PySlice>() {
+            let indices = slice.indices(self.len as std::os::raw::c_long)?;
+            if indices.step == 1 {
+                let records = self.range(indices.start as usize, indices.slicelength as usize);
+                return Ok(Py::new(py, records)?.into_ref(py));
+            }
+
+            let values = (0..indices.slicelength)
+                .map(|step| self.get(py, (indices.start + step * indices.step) as usize))
+                .collect::<PyResult<Vec<_>>>()?;
+            return Ok(PyList::new(py, values));
+        }
+
+        let index = index.extract::<isize>()?;
+        let position = match index < 0 {
+            true => index + self.len as isize,
+            false => index,
+        };
+        if position < 0 || position >= self.len as isize {
+            return Err(PyIndexError::new_err(format!(
+                "Index {index} is out of range for {} records",
+                self.len
+            )));
+        }
+        self.get(py, position as usize)
+    }
+
+    fn __iter__(slf: &PyCell<Self>) -> RecordIterator {
+        RecordIterator::new(slf.into())
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!(
+            "Records({}, {} records, {} bytes remaining)",
+            self.type_.as_ref(py).name()?,
+            self.len,
+            self.remainder.len()
+        ))
+    }
+}
+
+/// Iterates over the values of a `Records`, as returned by `iter_unpack`
+#[pyclass(module = "_cerialize", name = "RecordIterator")]
+pub struct RecordIterator {
+    records: Py<Records>,
+    position: usize,
+}
+
+impl RecordIterator {
+    pub fn new(records: Py<Records>) -> Self {
+        Self {
+            records,
+            position: 0,
+        }
+    }
+}
+
+#[pymethods]
+impl RecordIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__<'py>(&mut self, py: Python<'py>) -> PyResult<Option<&'py PyAny>> {
+        let records = self.records.get();
+        if self.position >= records.len {
+            return Ok(None);
+        }
+
+        self.position += 1;
+        records.get(py, self.position - 1).map(Some)
+    }
+}
@@ -1,91 +1,104 @@
-use pyo3::basic::CompareOp;
 use pyo3::prelude::*;
 
-#[pyclass(module = "_cerialize", frozen, weakref)]
-#[derive(Clone, Copy)]
-pub struct NativeEndian();
-
-#[pymethods]
-impl NativeEndian {
-    fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
-        let class_name: &str = slf.get_type().name()?;
-        Ok(class_name.to_string())
-    }
-
-    fn __richcmp__(&self, _other: &Self, op: CompareOp) -> PyResult<bool> {
-        match op {
-            CompareOp::Lt => Ok(false),
-            CompareOp::Le => Ok(true),
-            CompareOp::Eq => Ok(true),
-            CompareOp::Ne => Ok(false),
-            CompareOp::Gt => Ok(false),
-            CompareOp::Ge => Ok(true),
-        }
-    }
+/// The byte order of a value's packed representation.
+/// `NativeEndian`, `LittleEndian` and `BigEndian` are aliases of the members.
+#[pyclass(module = "_cerialize", name = "Endianness", frozen)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum Endianness {
+    #[default]
+    #[pyo3(name = "native")]
+    Native = 0,
+    #[pyo3(name = "little")]
+    Little = 1,
+    #[pyo3(name = "big")]
+    Big = 2,
 }
 
-#[pyclass(module = "_cerialize", frozen, weakref)]
-#[derive(Clone, Copy)]
-pub struct BigEndian();
+/// What happens when an integer doesn't fit into the type it is converted to
+#[pyclass(module = "_cerialize", name = "Overflow", frozen)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum Overflow {
+    /// Raise a `ValueRangeError`
+    #[default]
+    #[pyo3(name = "error")]
+    Error,
+    /// Keep the low bits of the value (two's complement truncation), the same as an integer cast in C
+    #[pyo3(name = "wrap")]
+    Wrap,
+    /// Clamp the value to the closest value the type can represent
+    #[pyo3(name = "saturate")]
+    Saturate,
+}
 
-#[pymethods]
-impl BigEndian {
-    fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
-        let class_name: &str = slf.get_type().name()?;
-        Ok(class_name.to_string())
-    }
+/// What happens when an enum is read or assigned a value which isn't the value of any of its members
+#[pyclass(module = "_cerialize", name = "UnknownValue", frozen)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum UnknownValue {
+    /// Raise a `ValueRangeError`
+    #[default]
+    #[pyo3(name = "error")]
+    Error,
+    /// Keep the value as a plain `int`
+    #[pyo3(name = "keep")]
+    Keep,
+}
 
-    fn __richcmp__(&self, _other: &Self, op: CompareOp) -> PyResult<bool> {
-        match op {
-            CompareOp::Lt => Ok(false),
-            CompareOp::Le => Ok(true),
-            CompareOp::Eq => Ok(true),
-            CompareOp::Ne => Ok(false),
-            CompareOp::Gt => Ok(false),
-            CompareOp::Ge => Ok(true),
+impl UnknownValue {
+    pub fn name(&self) -> &'static str {
+        match self {
+            UnknownValue::Error => "error",
+            UnknownValue::Keep => "keep",
         }
     }
 }
 
-#[pyclass(module = "_cerialize", frozen, weakref)]
-#[derive(Clone, Copy)]
-pub struct LittleEndian();
+/// The order in which consecutive bitfields are allocated within their storage unit
+#[pyclass(module = "_cerialize", name = "BitOrder", frozen)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum BitOrder {
+    /// The first bitfield takes the least significant bits, as GCC does on x86 and ARM
+    #[default]
+    #[pyo3(name = "lsb_first")]
+    LsbFirst,
+    /// The first bitfield takes the most significant bits, as is common on big-endian targets
+    #[pyo3(name = "msb_first")]
+    MsbFirst,
+}
 
-#[pymethods]
-impl LittleEndian {
-    fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
-        let class_name: &str = slf.get_type().name()?;
-        Ok(class_name.to_string())
+impl BitOrder {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BitOrder::LsbFirst => "lsb_first",
+            BitOrder::MsbFirst => "msb_first",
+        }
     }
+}
 
-    fn __richcmp__(&self, _other: &Self, op: CompareOp) -> PyResult<bool> {
-        match op {
-            CompareOp::Lt => Ok(false),
-            CompareOp::Le => Ok(true),
-            CompareOp::Eq => Ok(true),
-            CompareOp::Ne => Ok(false),
-            CompareOp::Gt => Ok(false),
-            CompareOp::Ge => Ok(true),
+impl Overflow {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Overflow::Error => "error",
+            Overflow::Wrap => "wrap",
+            Overflow::Saturate => "saturate",
         }
     }
 }
 
-#[derive(FromPyObject, Copy, Clone)]
-pub enum Endianness {
-    #[pyo3(transparent)]
-    Native(NativeEndian),
-    #[pyo3(transparent)]
-    Big(BigEndian),
-    #[pyo3(transparent)]
-    Little(LittleEndian),
-}
+impl Endianness {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Endianness::Native => "native",
+            Endianness::Little => "little",
+            Endianness::Big => "big",
+        }
+    }
 
-impl IntoPy<Py<pyo3::PyAny>> for Endianness {
-    fn into_py(self, py: Python<'_>) -> Py<pyo3::PyAny> {
+    /// Whether values in this byte order can be read without swapping their bytes
+    pub fn is_native(&self) -> bool {
         match self {
-            Endianness::Native(value) => Py::new(py, value).unwrap().as_ref(py).into(),
-            Endianness::Big(value) => Py::new(py, value).unwrap().as_ref(py).into(),
-            Endianness::Little(value) => Py::new(py, value).unwrap().as_ref(py).into(),
+            Endianness::Native => true,
+            Endianness::Little => cfg!(target_endian = "little"),
+            Endianness::Big => cfg!(target_endian = "big"),
         }
     }
 }
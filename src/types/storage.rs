@@ -0,0 +1,215 @@
+use pyo3::prelude::*;
+use pyo3::{ffi, AsPyPointer};
+
+use std::ffi::{c_int, c_void};
+use std::mem::MaybeUninit;
+
+/// Memory which is exported by another Python object through the buffer protocol
+struct Exported(Box<ffi::Py_buffer>);
+
+// The exporting object is kept alive (and its memory in place) until the buffer is released
+unsafe impl Send for Exported {}
+
+impl Exported {
+    /// Requests a contiguous buffer from `obj`, regardless of the format of its items
+    fn get(obj: &PyAny, flags: std::ffi::c_int) -> PyResult<Self> {
+        let mut buffer = Box::new(MaybeUninit::<ffi::Py_buffer>::uninit());
+        unsafe {
+            if ffi::PyObject_GetBuffer(obj.as_ptr(), buffer.as_mut_ptr(), flags) == -1 {
+                return Err(PyErr::fetch(obj.py()));
+            }
+            Ok(Self(Box::from_raw(Box::into_raw(buffer).cast())))
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.0.len as usize
+    }
+
+    fn bytes(&self) -> &[u8] {
+        match self.len() {
+            0 => &[],
+            len => unsafe { std::slice::from_raw_parts(self.0.buf as *const u8, len) },
+        }
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        match self.len() {
+            0 => &mut [],
+            len => unsafe { std::slice::from_raw_parts_mut(self.0.buf as *mut u8, len) },
+        }
+    }
+}
+
+impl Drop for Exported {
+    fn drop(&mut self) {
+        Python::with_gil(|_| unsafe { ffi::PyBuffer_Release(&mut *self.0) })
+    }
+}
+
+enum Memory {
+    Owned(Vec<u8>),
+    /// Writable memory belonging to another object, such as a `bytearray` or a writable `mmap`
+    Exported(Exported),
+}
+
+/// The memory backing a value and every value nested inside of it
+#[pyclass(module = "_cerialize", name = "Storage")]
+pub struct Storage {
+    memory: Memory,
+}
+
+impl Storage {
+    fn bytes(&self) -> &[u8] {
+        match &self.memory {
+            Memory::Owned(bytes) => bytes,
+            Memory::Exported(exported) => exported.bytes(),
+        }
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        match &mut self.memory {
+            Memory::Owned(bytes) => bytes,
+            Memory::Exported(exported) => exported.bytes_mut(),
+        }
+    }
+}
+
+/// Copies the contents of any object which supports the buffer protocol.
+/// Returns `None` if `obj` isn't bytes-like.
+pub fn bytes_of(obj: &PyAny) -> PyResult<Option<Vec<u8>>> {
+    if unsafe { ffi::PyObject_CheckBuffer(obj.as_ptr()) } == 0 {
+        return Ok(None);
+    }
+    Ok(Some(
+        Exported::get(obj, ffi::PyBUF_SIMPLE)?.bytes().to_vec(),
+    ))
+}
+
+/// Calls `f` with the memory of any object which supports the buffer protocol, without copying it.
+/// The memory is exported for the duration of the call, so `f` may release the GIL while it reads it.
+pub fn with_bytes<R>(obj: &PyAny, f: impl FnOnce(&[u8]) -> R) -> PyResult<R> {
+    let exported = Exported::get(obj, ffi::PyBUF_SIMPLE)?;
+    Ok(f(exported.bytes()))
+}
+
+/// A window into a `Storage`.
+/// Nested values share the storage of their parent so reading them doesn't copy and writing to them is visible in the parent.
+#[derive(Clone)]
+pub struct View {
+    storage: Py<Storage>,
+    offset: usize,
+    len: usize,
+}
+
+impl View {
+    /// Creates a view spanning the entirety of a new storage which owns `bytes`
+    pub fn new(py: Python<'_>, bytes: Vec<u8>) -> PyResult<Self> {
+        let len = bytes.len();
+        Ok(Self {
+            storage: Py::new(
+                py,
+                Storage {
+                    memory: Memory::Owned(bytes),
+                },
+            )?,
+            offset: 0,
+            len,
+        })
+    }
+
+    /// Creates a view of any bytes-like object.
+    /// Writable buffers are shared rather than copied so that modifications end up in the memory of `obj`.
+    pub fn of(obj: &PyAny) -> PyResult<Self> {
+        let py = obj.py();
+        match Exported::get(obj, ffi::PyBUF_WRITABLE) {
+            Ok(exported) => {
+                let len = exported.len();
+                Ok(Self {
+                    storage: Py::new(
+                        py,
+                        Storage {
+                            memory: Memory::Exported(exported),
+                        },
+                    )?,
+                    offset: 0,
+                    len,
+                })
+            }
+            // Read-only memory (such as `bytes`) is copied instead so the value can still be modified
+            Err(_) => Self::new(py, Exported::get(obj, ffi::PyBUF_SIMPLE)?.bytes().to_vec()),
+        }
+    }
+
+    /// Creates a view of `len` bytes starting at `offset` within this view
+    pub fn slice(&self, offset: usize, len: usize) -> Self {
+        assert!(offset + len <= self.len, "slice is out of bounds");
+        Self {
+            storage: self.storage.clone(),
+            offset: self.offset + offset,
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether both views are windows into the same storage
+    pub fn shares_storage(&self, other: &View) -> bool {
+        self.storage.is(&other.storage)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn read<R>(&self, py: Python<'_>, f: impl FnOnce(&[u8]) -> R) -> R {
+        let storage = self.storage.borrow(py);
+        f(&storage.bytes()[self.offset..self.offset + self.len])
+    }
+
+    pub fn to_vec(&self, py: Python<'_>) -> Vec<u8> {
+        self.read(py, |bytes| bytes.to_vec())
+    }
+
+    /// Returns a pointer to the first byte of the view.
+    /// The pointer stays valid for as long as the storage is alive since the storage is never resized.
+    pub fn as_mut_ptr(&self, py: Python<'_>) -> *mut u8 {
+        let mut storage = self.storage.borrow_mut(py);
+        storage.bytes_mut()[self.offset..].as_mut_ptr()
+    }
+
+    /// Fills `view` with the memory of this view for a `__getbuffer__` request, which is read-only unless `flags` asks for a writable buffer.
+    /// The storage rather than the requesting value becomes `view.obj`, so the memory outlives the value if it is reallocated or freed while the buffer is held.
+    ///
+    /// # Safety
+    /// `view` must point to the `Py_buffer` passed to `__getbuffer__`
+    pub unsafe fn export(
+        &self,
+        py: Python<'_>,
+        view: *mut ffi::Py_buffer,
+        flags: c_int,
+    ) -> PyResult<()> {
+        let result = ffi::PyBuffer_FillInfo(
+            view,
+            self.storage.as_ptr(),
+            self.as_mut_ptr(py) as *mut c_void,
+            self.len as ffi::Py_ssize_t,
+            c_int::from(flags & ffi::PyBUF_WRITABLE == 0),
+            flags,
+        );
+
+        match result {
+            0 => Ok(()),
+            _ => Err(PyErr::fetch(py)),
+        }
+    }
+
+    /// Overwrites the bytes starting at `offset` within this view
+    pub fn write(&self, py: Python<'_>, offset: usize, bytes: &[u8]) {
+        assert!(offset + bytes.len() <= self.len, "write is out of bounds");
+        let start = self.offset + offset;
+        self.storage.borrow_mut(py).bytes_mut()[start..start + bytes.len()].copy_from_slice(bytes);
+    }
+}
@@ -0,0 +1,522 @@
+use super::{bytes_of, CEnum, Endianness, PascalString, PrefixedBytes, PyShaped};
+use crate::errors::{BUFFER_SIZE_ERROR, LAYOUT_ERROR, VALUE_RANGE_ERROR};
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString, PyTuple, PyType};
+
+/// Decodes the packed value in `buffer` if `type_` is one of the types which are read as plain Python objects rather than as views,
+/// i.e. `str` for the string types, `bytes` for `bytes_prefixed` and members of the enum for `cenum`
+pub fn plain_value<'py>(
+    type_: &PyType,
+    py: Python<'py>,
+    buffer: &[u8],
+    endianness: Endianness,
+) -> PyResult<Option<&'py PyAny>> {
+    if type_.is_subclass_of::<CStr>()? {
+        return Ok(Some(CStr::text(type_, py, buffer)?));
+    }
+    if type_.is_subclass_of::<CString>()? {
+        return Ok(Some(CString::text(type_, py, buffer)?));
+    }
+    if type_.is_subclass_of::<PascalString>()? {
+        return Ok(Some(PascalString::text(type_, py, buffer, endianness)?));
+    }
+    if type_.is_subclass_of::<PrefixedBytes>()? {
+        return Ok(Some(PrefixedBytes::payload_of(
+            type_, py, buffer, endianness,
+        )?));
+    }
+    if type_.is_subclass_of::<CEnum>()? {
+        return Ok(Some(CEnum::value_of(type_, py, buffer, endianness)?));
+    }
+    Ok(None)
+}
+
+/// The character encodings which a `cstr` can hold, all of which encode the null character as a single zero byte
+#[pyclass(module = "_cerialize", name = "Encoding", frozen)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum Encoding {
+    #[default]
+    #[pyo3(name = "ascii")]
+    Ascii,
+    #[pyo3(name = "utf8")]
+    Utf8,
+    #[pyo3(name = "latin1")]
+    Latin1,
+}
+
+impl Encoding {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Ascii => "ascii",
+            Encoding::Utf8 => "utf8",
+            Encoding::Latin1 => "latin1",
+        }
+    }
+
+    /// The name of the Python codec which implements the encoding
+    fn codec(&self) -> &'static str {
+        match self {
+            Encoding::Ascii => "ascii",
+            Encoding::Utf8 => "utf-8",
+            Encoding::Latin1 => "latin-1",
+        }
+    }
+
+    pub fn decode<'py>(&self, py: Python<'py>, buffer: &[u8]) -> PyResult<&'py PyString> {
+        Ok(PyBytes::new(py, buffer)
+            .call_method1("decode", (self.codec(),))?
+            .downcast::<PyString>()?)
+    }
+
+    pub fn encode(&self, text: &PyString) -> PyResult<Vec<u8>> {
+        Ok(text
+            .call_method1("encode", (self.codec(),))?
+            .downcast::<PyBytes>()?
+            .as_bytes()
+            .to_vec())
+    }
+
+    /// Accepts any of the names Python knows the supported encodings by, e.g. `"utf-8"`, `"UTF8"` or `"iso-8859-1"`
+    pub fn from_name(name: &PyString) -> PyResult<Self> {
+        let py = name.py();
+        let codec = py
+            .import("codecs")?
+            .call_method1("lookup", (name,))?
+            .getattr("name")?
+            .extract::<String>()?;
+        match codec.as_str() {
+            "ascii" => Ok(Encoding::Ascii),
+            "utf-8" => Ok(Encoding::Utf8),
+            "iso8859-1" => Ok(Encoding::Latin1),
+            _ => Err(LAYOUT_ERROR.new_err(format!(
+                "Strings can only be encoded as ASCII, UTF-8 or Latin-1, got {name}"
+            ))),
+        }
+    }
+}
+
+/// How the text of a `cstr` ends when it is shorter than the string
+#[pyclass(module = "_cerialize", name = "Termination", frozen)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum Termination {
+    /// The text is always followed by a null character, so it is at most one byte shorter than the string (e.g. `char name[32]`)
+    #[default]
+    #[pyo3(name = "terminated")]
+    Terminated,
+    /// The text may fill the whole string and is only followed by null characters if it is shorter (e.g. `strncpy`)
+    #[pyo3(name = "padded")]
+    Padded,
+}
+
+impl Termination {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Termination::Terminated => "terminated",
+            Termination::Padded => "padded",
+        }
+    }
+}
+
+/// A fixed-length string of characters, created by subscripting `cstr` (or its alias `char`) with its length in bytes.
+/// The encoding and termination follow the length, e.g. `cstr[32]`, `cstr[32, "utf-8"]` or `cstr[16, "latin-1", Termination.padded]`.
+/// The text ends at the first null character and the remainder of the string is padded with null characters.
+#[pyclass(module = "_cerialize", name = "cstr", subclass, weakref, extends=PyShaped)]
+pub struct CStr();
+
+impl CStr {
+    /// Returns the length, encoding and termination of a string class
+    fn options(cls: &PyType) -> PyResult<(usize, Encoding, Termination)> {
+        let length = match cls.getattr("_LENGTH") {
+            Ok(length) if !length.is_none() => length.extract::<usize>()?,
+            _ => {
+                return Err(LAYOUT_ERROR.new_err(format!(
+                    "{} doesn't have a length, create strings by subscripting it, e.g. cstr[32]",
+                    cls.name()?
+                )))
+            }
+        };
+
+        Ok((
+            length,
+            cls.getattr("_ENCODING")?.extract::<Encoding>()?,
+            cls.getattr("_TERMINATION")?.extract::<Termination>()?,
+        ))
+    }
+
+    /// Decodes the text of a packed string, which is everything up to the first null character
+    pub fn text<'py>(cls: &PyType, py: Python<'py>, buffer: &[u8]) -> PyResult<&'py PyString> {
+        let (_, encoding, _) = Self::options(cls)?;
+        let end = buffer
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(buffer.len());
+        encoding.decode(py, &buffer[..end])
+    }
+
+    /// Encodes `text` into the packed representation of a string of type `cls`
+    fn encode_text(cls: &PyType, text: &PyString) -> PyResult<Vec<u8>> {
+        let (length, encoding, termination) = Self::options(cls)?;
+        let mut buffer = encoding.encode(text)?;
+
+        let capacity = match termination {
+            Termination::Terminated => length.saturating_sub(1),
+            Termination::Padded => length,
+        };
+        if buffer.len() > capacity || buffer.contains(&0) {
+            return Err(VALUE_RANGE_ERROR.new_err(format!(
+                "{} holds text of at most {capacity} bytes without null characters, got {:?} ({} bytes)",
+                cls.name()?,
+                text,
+                buffer.len()
+            )));
+        }
+
+        buffer.resize(length, 0);
+        Ok(buffer)
+    }
+
+    fn value(slf: &PyCell<Self>) -> PyResult<&PyString> {
+        let py = slf.py();
+        let shaped = slf.borrow();
+        let shaped = shaped.as_ref();
+        shaped
+            .buffer
+            .read(py, |buffer| Self::text(slf.get_type(), py, buffer))
+    }
+}
+
+#[pymethods]
+impl CStr {
+    /// Creates an empty string, or one holding `value` which is either a `str` or the packed bytes of the string
+    #[new]
+    #[classmethod]
+    #[pyo3(signature = (value = None, endianness = None))]
+    fn new(
+        cls: &PyType,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let (length, _, _) = Self::options(cls)?;
+        let buffer = match value {
+            Some(value) => {
+                if let Ok(text) = value.downcast::<PyString>() {
+                    Self::encode_text(cls, text)?
+                } else if let Some(buffer) = bytes_of(value)? {
+                    if buffer.len() != length {
+                        return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                            "{} requires a buffer of exactly {length} bytes, got {}",
+                            cls.name()?,
+                            buffer.len()
+                        )));
+                    }
+                    buffer
+                } else {
+                    return Err(PyTypeError::new_err(format!(
+                        "{} can't be created from a value of type {}",
+                        cls.name()?,
+                        value.get_type().name()?
+                    )));
+                }
+            }
+            None => vec![0; length],
+        };
+
+        // Characters don't have a byte order, it is only kept so that the string matches the struct it is part of
+        Ok((
+            Self(),
+            PyShaped::with_buffer(cls.py(), buffer, endianness.unwrap_or_default())?,
+        ))
+    }
+
+    /// `cstr[length, encoding, termination]` creates a string type, subscripting a string type creates an array of strings
+    #[pyo3(signature = (*args))]
+    #[classmethod]
+    fn __class_getitem__(cls: &PyType, args: &PyTuple) -> PyResult<Py<PyType>> {
+        let py = cls.py();
+        if Self::options(cls).is_ok() {
+            return PyShaped::__class_getitem__(cls, py, args);
+        }
+
+        let arg = args.get_item(0)?;
+        let options = match arg.downcast::<PyTuple>() {
+            Ok(options) => options.iter().collect::<Vec<_>>(),
+            Err(_) => vec![arg],
+        };
+        let Some((length, options)) = options.split_first() else {
+            return Err(LAYOUT_ERROR.new_err("The length of a string is required"));
+        };
+        let length = length.extract::<usize>().map_err(|_| {
+            LAYOUT_ERROR.new_err(format!(
+                "The length of a string must be a non-negative integer, got {length}"
+            ))
+        })?;
+
+        let (mut encoding, mut termination) = (Encoding::default(), Termination::default());
+        for option in options {
+            if let Ok(option) = option.extract::<Encoding>() {
+                encoding = option;
+            } else if let Ok(option) = option.extract::<Termination>() {
+                termination = option;
+            } else if let Ok(name) = option.downcast::<PyString>() {
+                encoding = Encoding::from_name(name)?;
+            } else {
+                return Err(LAYOUT_ERROR.new_err(format!(
+                    "Unsupported string option {option}, expected an encoding or a Termination"
+                )));
+            }
+        }
+
+        // Only the options which differ from the defaults are part of the name, e.g. `cstr[32,utf8]`
+        let mut parameters = vec![length.to_string()];
+        if encoding != Encoding::default() {
+            parameters.push(encoding.name().to_string());
+        }
+        if termination != Termination::default() {
+            parameters.push(termination.name().to_string());
+        }
+        let class_name = format!("{}[{}]", cls.name()?, parameters.join(","));
+
+        PyShaped::derived_type(cls, class_name, |class_name| {
+            let new_type = py
+                .import("types")?
+                .getattr("new_class")?
+                .call1((class_name, (cls,)))?
+                .downcast::<PyType>()?;
+            new_type.setattr("__module__", cls.getattr("__module__")?)?;
+            new_type.setattr("_LENGTH", length)?;
+            new_type.setattr("_ENCODING", encoding.into_py(py))?;
+            new_type.setattr("_TERMINATION", termination.into_py(py))?;
+            Ok(new_type.into())
+        })
+    }
+
+    fn __str__(slf: &PyCell<Self>) -> PyResult<&PyString> {
+        Self::value(slf)
+    }
+
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
+        Ok(format!(
+            "{}({})",
+            slf.get_type().name()?,
+            Self::value(slf)?.repr()?
+        ))
+    }
+
+    /// Strings compare by their text, both with each other and with `str`
+    fn __richcmp__(slf: &PyCell<Self>, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        let py = slf.py();
+        let other = match other.downcast::<PyCell<Self>>() {
+            Ok(other) => Self::value(other)?,
+            Err(_) => match other.downcast::<PyString>() {
+                Ok(other) => other,
+                Err(_) => return Ok(py.NotImplemented()),
+            },
+        };
+        Ok(Self::value(slf)?.rich_compare(other, op)?.into())
+    }
+
+    fn __hash__(slf: &PyCell<Self>) -> PyResult<isize> {
+        Self::value(slf)?.hash()
+    }
+
+    fn __len__(slf: &PyCell<Self>) -> PyResult<usize> {
+        Self::value(slf)?.len()
+    }
+
+    #[classmethod]
+    fn __packed_size__(cls: &PyType) -> PyResult<usize> {
+        Ok(Self::options(cls)?.0)
+    }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(1)
+    }
+
+    /// Returns the equivalent NumPy dtype, a bytes string of the same length
+    #[classmethod]
+    #[pyo3(signature = (_endianness = None))]
+    fn dtype(cls: &PyType, _endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        let (length, _, _) = Self::options(cls)?;
+        cls.py()
+            .import("numpy")?
+            .getattr("dtype")?
+            .call1((format!("S{length}"),))
+    }
+}
+
+/// A null-terminated string whose size is determined by its contents rather than by its type, e.g. a `const char *` in a string table.
+/// Structs with a `cstring` field are dynamically sized: the offsets of the fields after it depend on the length of its text.
+/// The encoding is ASCII unless it is given by subscripting, e.g. `cstring["utf-8"]`.
+#[pyclass(module = "_cerialize", name = "cstring", subclass, weakref, extends=PyShaped)]
+pub struct CString();
+
+impl CString {
+    fn encoding(cls: &PyType) -> PyResult<Encoding> {
+        match cls.getattr("_ENCODING") {
+            Ok(encoding) => encoding.extract::<Encoding>(),
+            Err(_) => Ok(Encoding::default()),
+        }
+    }
+
+    /// Returns the size of the string at the start of `buffer`, including its terminator
+    pub fn measure(cls: &PyType, buffer: &[u8]) -> PyResult<usize> {
+        match buffer.iter().position(|&byte| byte == 0) {
+            Some(end) => Ok(end + 1),
+            None => Err(BUFFER_SIZE_ERROR.new_err(format!(
+                "{} requires a null character to end it, none found in {} bytes",
+                cls.name()?,
+                buffer.len()
+            ))),
+        }
+    }
+
+    pub fn text<'py>(cls: &PyType, py: Python<'py>, buffer: &[u8]) -> PyResult<&'py PyString> {
+        let end = Self::measure(cls, buffer)? - 1;
+        Self::encoding(cls)?.decode(py, &buffer[..end])
+    }
+
+    fn value(slf: &PyCell<Self>) -> PyResult<&PyString> {
+        let py = slf.py();
+        let shaped = slf.borrow();
+        let shaped = shaped.as_ref();
+        shaped
+            .buffer
+            .read(py, |buffer| Self::text(slf.get_type(), py, buffer))
+    }
+}
+
+#[pymethods]
+impl CString {
+    /// Creates an empty string, or one holding `value` which is either a `str` or the packed bytes of the string (ending in its only null character)
+    #[new]
+    #[classmethod]
+    #[pyo3(signature = (value = None, endianness = None))]
+    fn new(
+        cls: &PyType,
+        value: Option<&PyAny>,
+        endianness: Option<Endianness>,
+    ) -> PyResult<(Self, PyShaped)> {
+        let buffer = match value {
+            Some(value) => {
+                if let Ok(text) = value.downcast::<PyString>() {
+                    let mut buffer = Self::encoding(cls)?.encode(text)?;
+                    if buffer.contains(&0) {
+                        return Err(VALUE_RANGE_ERROR.new_err(format!(
+                            "{} can't hold text with null characters, got {:?}",
+                            cls.name()?,
+                            text
+                        )));
+                    }
+                    buffer.push(0);
+                    buffer
+                } else if let Some(buffer) = bytes_of(value)? {
+                    if Self::measure(cls, &buffer)? != buffer.len() {
+                        return Err(BUFFER_SIZE_ERROR.new_err(format!(
+                            "{} requires a buffer which ends at its first null character, got {} bytes",
+                            cls.name()?,
+                            buffer.len()
+                        )));
+                    }
+                    buffer
+                } else {
+                    return Err(PyTypeError::new_err(format!(
+                        "{} can't be created from a value of type {}",
+                        cls.name()?,
+                        value.get_type().name()?
+                    )));
+                }
+            }
+            None => vec![0],
+        };
+
+        Ok((
+            Self(),
+            PyShaped::with_buffer(cls.py(), buffer, endianness.unwrap_or_default())?,
+        ))
+    }
+
+    /// `cstring[encoding]` creates a string type with another encoding
+    #[classmethod]
+    fn __class_getitem__(cls: &PyType, encoding: &PyAny) -> PyResult<Py<PyType>> {
+        let py = cls.py();
+        let name = cls.name()?;
+        let encoding = match encoding.downcast::<PyString>() {
+            Ok(encoding) => Encoding::from_name(encoding)?,
+            Err(_) => encoding.extract::<Encoding>().map_err(|_| {
+                LAYOUT_ERROR.new_err(format!(
+                    "{name} is dynamically sized, so it can only be subscripted with an encoding, got {encoding}"
+                ))
+            })?,
+        };
+
+        let class_name = format!("{name}[{}]", encoding.name());
+        PyShaped::derived_type(cls, class_name, |class_name| {
+            let new_type = py
+                .import("types")?
+                .getattr("new_class")?
+                .call1((class_name, (cls,)))?
+                .downcast::<PyType>()?;
+            new_type.setattr("__module__", cls.getattr("__module__")?)?;
+            new_type.setattr("_ENCODING", encoding.into_py(py))?;
+            Ok(new_type.into())
+        })
+    }
+
+    fn __str__(slf: &PyCell<Self>) -> PyResult<&PyString> {
+        Self::value(slf)
+    }
+
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
+        Ok(format!(
+            "{}({})",
+            slf.get_type().name()?,
+            Self::value(slf)?.repr()?
+        ))
+    }
+
+    /// Strings compare by their text, both with each other and with `str`
+    fn __richcmp__(slf: &PyCell<Self>, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        let py = slf.py();
+        let other = match other.downcast::<PyCell<Self>>() {
+            Ok(other) => Self::value(other)?,
+            Err(_) => match other.downcast::<PyString>() {
+                Ok(other) => other,
+                Err(_) => return Ok(py.NotImplemented()),
+            },
+        };
+        Ok(Self::value(slf)?.rich_compare(other, op)?.into())
+    }
+
+    fn __hash__(slf: &PyCell<Self>) -> PyResult<isize> {
+        Self::value(slf)?.hash()
+    }
+
+    fn __len__(slf: &PyCell<Self>) -> PyResult<usize> {
+        Self::value(slf)?.len()
+    }
+
+    #[classmethod]
+    fn __packed_size__(cls: &PyType) -> PyResult<usize> {
+        Err(LAYOUT_ERROR.new_err(format!(
+            "{} is dynamically sized, the size of a value depends on its text",
+            cls.name()?
+        )))
+    }
+
+    #[classmethod]
+    fn __alignment__(_cls: &PyType) -> PyResult<usize> {
+        Ok(1)
+    }
+
+    #[classmethod]
+    #[pyo3(signature = (_endianness = None))]
+    fn dtype(cls: &PyType, _endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        Err(PyTypeError::new_err(format!(
+            "NumPy has no equivalent of {}",
+            cls.name()?
+        )))
+    }
+}
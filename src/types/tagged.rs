@@ -0,0 +1,196 @@
+use super::{CStruct, Endianness, PyShaped};
+use crate::errors::{LAYOUT_ERROR, UNKNOWN_TAG_ERROR};
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyString, PyTuple, PyType};
+
+use itertools::Itertools;
+
+/// A payload whose type is selected by an integer field declared before it in the same struct, e.g. `tagged["kind", {1: ping, 2: pong}]`.
+/// Assigning a payload sets the tag to the one of its variant, and assigning the tag replaces the payload with an empty value of the new variant.
+/// Tags which don't select any variant raise an `UnknownTagError`, unless `bytes` follows the variants (`tagged["kind", {...}, bytes]`):
+/// then the payload is read as the raw bytes up to the end of the buffer.
+#[pyclass(module = "_cerialize", name = "tagged", subclass, weakref, extends=PyShaped)]
+pub struct Tagged();
+
+impl Tagged {
+    /// Returns the name of the field holding the tag, or `None` if `cls` hasn't been subscripted
+    pub fn tag_of(cls: &PyType) -> PyResult<Option<String>> {
+        match cls.getattr("_TAG") {
+            Ok(tag) if !tag.is_none() => Ok(Some(tag.extract::<String>()?)),
+            _ => Ok(None),
+        }
+    }
+
+    fn variants(cls: &PyType) -> PyResult<&PyDict> {
+        match cls.getattr("_VARIANTS") {
+            Ok(variants) if !variants.is_none() => Ok(variants.downcast::<PyDict>()?),
+            _ => Err(LAYOUT_ERROR.new_err(format!(
+                "{name} doesn't have any variants, create it by subscripting it with the name of its tag and its variants, e.g. {name}[\"kind\", {{1: ping, 2: pong}}]",
+                name = cls.name()?
+            ))),
+        }
+    }
+
+    fn fallback(cls: &PyType) -> PyResult<bool> {
+        match cls.getattr("_FALLBACK") {
+            Ok(fallback) => fallback.is_true(),
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// Returns the type of the payload selected by `tag`, or `None` if the tag is unknown and the payload is read as raw bytes
+    pub fn variant<'py>(cls: &'py PyType, tag: &PyAny) -> PyResult<Option<&'py PyType>> {
+        match Self::variants(cls)?.get_item(tag) {
+            Some(variant) => Ok(Some(variant.downcast::<PyType>()?)),
+            None if Self::fallback(cls)? => Ok(None),
+            None => Err(UNKNOWN_TAG_ERROR.new_err(format!(
+                "{tag} isn't the tag of any variant of {}",
+                cls.name()?
+            ))),
+        }
+    }
+
+    /// Returns the tag and type of the variant which `value` is an instance of, preferring an exact match over a subclass
+    pub fn tag_for<'py>(cls: &'py PyType, value: &PyAny) -> PyResult<(&'py PyAny, &'py PyType)> {
+        let variants = Self::variants(cls)?
+            .iter()
+            .map(|(tag, variant)| Ok((tag, variant.downcast::<PyType>()?)))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        if let Some(&found) = variants
+            .iter()
+            .find(|(_, variant)| value.get_type().is(*variant))
+        {
+            return Ok(found);
+        }
+        for &(tag, variant) in &variants {
+            if value.is_instance(variant)? {
+                return Ok((tag, variant));
+            }
+        }
+
+        Err(PyTypeError::new_err(format!(
+            "Expected an instance of one of the variants of {} ({}), got {}",
+            cls.name()?,
+            variants
+                .iter()
+                .map(|(_, variant)| variant.name())
+                .collect::<PyResult<Vec<_>>>()?
+                .join(", "),
+            value.get_type().name()?
+        )))
+    }
+}
+
+#[pymethods]
+impl Tagged {
+    /// `tagged[tag, variants]` creates the type whose variant is selected by the field named `tag`
+    #[classmethod]
+    fn __class_getitem__(cls: &PyType, args: &PyAny) -> PyResult<Py<PyType>> {
+        let py = cls.py();
+        let name = cls.name()?;
+        let args = match args.downcast::<PyTuple>() {
+            Ok(args) => args.as_slice(),
+            Err(_) => std::slice::from_ref(&args),
+        };
+        let (tag, declared, fallback) = match args {
+            [tag, variants] => (tag, variants, false),
+            [tag, variants, fallback] if fallback.is(py.get_type::<PyBytes>()) => {
+                (tag, variants, true)
+            }
+            _ => {
+                return Err(LAYOUT_ERROR.new_err(format!(
+                    "{name} must be subscripted with the name of its tag and a dict of its variants, optionally followed by bytes"
+                )))
+            }
+        };
+        let tag = tag.downcast::<PyString>().map_err(|_| {
+            LAYOUT_ERROR.new_err(format!(
+                "The tag of {name} must be the name of a field, got {tag}"
+            ))
+        })?;
+        let declared = declared.downcast::<PyDict>().map_err(|_| {
+            LAYOUT_ERROR.new_err(format!(
+                "The variants of {name} must be a dict from tags to cstruct types, got {declared}"
+            ))
+        })?;
+
+        // The tags are normalized to `int` so that they can be looked up by the value of the tag field
+        let variants = PyDict::new(py);
+        for (value, variant) in declared {
+            let value = match value.hasattr("__index__")? {
+                true => value.call_method0("__index__")?,
+                false => {
+                    return Err(LAYOUT_ERROR
+                        .new_err(format!("The tags of {name} must be integers, got {value}")))
+                }
+            };
+            match variant.downcast::<PyType>() {
+                Ok(variant) if variant.is_subclass_of::<CStruct>()? => {
+                    variants.set_item(value, variant)?
+                }
+                _ => {
+                    return Err(LAYOUT_ERROR.new_err(format!(
+                        "The variants of {name} must be cstruct types, got {variant} for the tag {value}"
+                    )))
+                }
+            }
+        }
+
+        let class_name = format!(
+            "{name}[{tag}, {{{}}}{}]",
+            variants
+                .iter()
+                .map(|(value, variant)| Ok(format!(
+                    "{value}: {}",
+                    variant.downcast::<PyType>()?.name()?
+                )))
+                .collect::<PyResult<Vec<_>>>()?
+                .iter()
+                .format(", "),
+            if fallback { ", bytes" } else { "" }
+        );
+        // Variants which share a name are still different variants
+        let parameters = variants.values().iter().collect::<Vec<_>>();
+        PyShaped::derived_type_of(cls, class_name, &parameters, |class_name| {
+            let new_type = py
+                .import("types")?
+                .getattr("new_class")?
+                .call1((class_name, (cls,)))?
+                .downcast::<PyType>()?;
+            new_type.setattr("__module__", cls.getattr("__module__")?)?;
+            new_type.setattr("_TAG", tag)?;
+            new_type.setattr("_VARIANTS", variants)?;
+            new_type.setattr("_FALLBACK", fallback)?;
+            Ok(new_type.into())
+        })
+    }
+
+    #[classmethod]
+    fn __packed_size__(cls: &PyType) -> PyResult<usize> {
+        Err(LAYOUT_ERROR.new_err(format!(
+            "{} is dynamically sized, the size of a value depends on its tag",
+            cls.name()?
+        )))
+    }
+
+    /// The payload is aligned for every variant, the same way a union of them would be
+    #[classmethod]
+    fn __alignment__(cls: &PyType) -> PyResult<usize> {
+        let mut alignment = 1;
+        for variant in Self::variants(cls)?.values() {
+            alignment = alignment.max(variant.call_method0("__alignment__")?.extract::<usize>()?);
+        }
+        Ok(alignment)
+    }
+
+    #[classmethod]
+    #[pyo3(signature = (_endianness = None))]
+    fn dtype(cls: &PyType, _endianness: Option<Endianness>) -> PyResult<&PyAny> {
+        Err(PyTypeError::new_err(format!(
+            "NumPy has no equivalent of {}",
+            cls.name()?
+        )))
+    }
+}
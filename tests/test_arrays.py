@@ -0,0 +1,110 @@
+import pytest
+from _cerialize import Array, cstruct, f32, i16, u8, u16, BigEndian, LittleEndian
+from _cerialize.errors import ShapeError
+
+
+class record(cstruct):
+    _CFIELDS = {"kind": u8, "length": u16}
+
+
+def test_array_types():
+    assert issubclass(f32[2], Array)
+    assert f32[2] is f32[2]
+    assert f32[2].__origin__ is f32
+    assert f32[2]._SHAPE == (2,)
+    assert u8[2, 3].__packed_size__() == 6
+    assert record[4].__packed_size__() == 4 * record.__packed_size__()
+    assert record[4].__alignment__() == record.__alignment__()
+
+
+def test_one_dimension():
+    values = f32[2]([1.5, 2.5])
+
+    assert len(values) == 2
+    assert values.shape == (2,)
+    assert values[0] == f32(1.5)
+    assert values[-1] == f32(2.5)
+    assert [float(value) for value in values] == [1.5, 2.5]
+    assert repr(values) == "f32[2]([f32(1.5), f32(2.5)])"
+
+    with pytest.raises(IndexError):
+        values[2]
+
+
+def test_multiple_dimensions():
+    matrix = u8[2, 3](bytes(range(6)))
+
+    assert len(matrix) == 2
+    assert matrix.shape == (2, 3)
+    assert type(matrix[1]) is u8[3]
+    assert bytes(matrix[1]) == bytes([3, 4, 5])
+    assert matrix[1, 2] == u8(5)
+    assert matrix[1][2] == u8(5)
+    assert repr(matrix) == "u8[2,3]([[u8(0), u8(1), u8(2)], [u8(3), u8(4), u8(5)]])"
+
+
+def test_slices():
+    values = u8[5](bytes(range(5)))
+
+    assert type(values[1:3]) is u8[2]
+    assert bytes(values[1:3]) == bytes([1, 2])
+    assert values[::2] == [u8(0), u8(2), u8(4)]
+    assert u8[2, 3](bytes(range(6)))[:, 1] == [u8(1), u8(4)]
+
+
+def test_elements_are_views():
+    values = u16[2]()
+    values[1:][0] = 7
+
+    assert values[1] == u16(7)
+
+
+def test_setitem():
+    matrix = i16[2, 2]()
+    matrix[0] = [1, 2]
+    matrix[1, 0] = -3
+    matrix[:, 1] = [4, 5]
+
+    assert bytes(matrix) == bytes(i16[2, 2]([[1, 4], [-3, 5]]))
+
+    with pytest.raises(ShapeError):
+        matrix[:, 1] = [1, 2, 3]
+
+
+def test_element_endianness():
+    values = u16[2].from_buffer(b"\x00\x01\x00\x02", BigEndian)
+    values[0] = 0x0304
+
+    assert values[1] == u16(2)
+    assert bytes(values) == b"\x03\x04\x00\x02"
+    assert bytes(u16[2]([1, 2], LittleEndian)) == b"\x01\x00\x02\x00"
+
+
+def test_arrays_of_structs():
+    records = record[2]()
+    records[1].length = 5
+
+    assert records[1].length == u16(5)
+    assert bytes(records)[record.__packed_size__() + 2 :] == b"\x05\x00"
+
+
+def test_array_fields():
+    class samples(cstruct):
+        _CFIELDS = {"values": f32[2], "matrix": i16[2, 2]}
+
+    value = samples()
+    value.values = [1, 2]
+    value.values[0] = 3.5
+    value.matrix[1, 0] = -2
+
+    assert [float(x) for x in value.values] == [3.5, 2.0]
+    assert value.matrix[1, 0] == i16(-2)
+    assert bytes(value.matrix) == bytes(i16[2, 2]([[0, 0], [-2, 0]]))
+
+
+def test_invalid_arrays():
+    with pytest.raises(ShapeError):
+        u8[2]([1])
+
+    with pytest.raises(ShapeError):
+        Array.__packed_size__()
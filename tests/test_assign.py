@@ -0,0 +1,59 @@
+import pytest
+from _cerialize import cstruct, boolean, u8, i16, u32, f32, BigEndian, LittleEndian
+
+
+class header(cstruct):
+    _CFIELDS = {"flag": boolean, "kind": u8, "length": u32}
+
+
+class packet(cstruct):
+    _CFIELDS = {"header": header, "samples": i16[2, 2], "gain": f32}
+
+
+def test_assign_primitive_fields():
+    value = header()
+    value.flag = True
+    value.kind = 7
+    value.length = 0x01020304
+
+    assert value.flag == boolean(True)
+    assert value.kind == u8(7)
+    assert value.length == u32(0x01020304)
+
+
+def test_assign_encodes_with_struct_endianness():
+    value = header.from_buffer(bytes(8), BigEndian)
+    value.length = 0x01020304
+
+    assert value.length == u32(0x01020304)
+    assert value._offset_of("length") == 4
+    assert header.from_buffer(bytes(4) + bytes([1, 2, 3, 4]), BigEndian).length == u32(0x01020304)
+
+
+def test_assign_nested_struct():
+    inner = header.from_buffer(bytes(8), LittleEndian)
+    inner.kind = 3
+    inner.length = 42
+
+    value = packet.from_buffer(bytes(packet.__packed_size__()), BigEndian)
+    value.header = inner
+
+    assert value.header.kind == u8(3)
+    assert value.header.length == u32(42)
+
+
+def test_assign_whole_array():
+    value = packet()
+    value.samples = [[1, -2], [3, -4]]
+
+    with pytest.raises(ValueError):
+        value.samples = [1, 2, 3, 4]
+
+
+def test_assign_other_attributes():
+    value = header()
+    value.note = "not a field"
+
+    assert value.note == "not a field"
+    with pytest.raises(AttributeError):
+        del value.kind
@@ -0,0 +1,114 @@
+import pytest
+from typing import Annotated
+import cerialize
+from _cerialize import cstruct, field, f32, i8, u8, u16, BigEndian, BitOrder, Overflow
+from cerialize.errors import LayoutError, ValueRangeError
+
+
+class header(cstruct):
+    _CFIELDS = {"version": field(u8, bits=4), "ihl": field(u8, bits=4), "length": u16}
+    _ENDIANNESS = BigEndian
+
+
+class msb_header(header):
+    _CFIELDS = header._CFIELDS
+    _BIT_ORDER = BitOrder.msb_first
+
+
+def test_bitfields_share_a_storage_unit():
+    assert header.__packed_size__() == 4
+    assert header.__layout__().fields == [
+        ("version", u8, 0, 1),
+        ("ihl", u8, 0, 1),
+        ("length", u16, 2, 2),
+    ]
+
+    class split(cstruct):
+        _CFIELDS = {"a": field(u8, bits=6), "b": field(u8, bits=4), "c": field(u16, bits=1)}
+
+    assert split.__layout__().fields == [("a", u8, 0, 1), ("b", u8, 1, 1), ("c", u16, 2, 2)]
+
+
+def test_bit_order():
+    value = header.from_buffer(b"\x45\x00\x00\x14")
+    assert value.version == 5
+    assert value.ihl == 4
+    assert value.length == u16(20)
+
+    value = msb_header.from_buffer(b"\x45\x00\x00\x14")
+    assert value.version == 4
+    assert value.ihl == 5
+
+
+def test_assign_bitfields():
+    value = msb_header.from_buffer(b"\x45\x00\x00\x14")
+    value.ihl = 15
+    assert bytes(value) == b"\x4f\x00\x00\x14"
+
+    value.version = 6
+    assert bytes(value) == b"\x6f\x00\x00\x14"
+
+
+def test_bitfields_in_a_big_endian_unit():
+    class word(cstruct):
+        _CFIELDS = {"low": field(u16, bits=12), "high": field(u16, bits=4)}
+        _ENDIANNESS = BigEndian
+
+    value = word()
+    value.low = 0xABC
+    value.high = 0xD
+    assert bytes(value) == b"\xda\xbc"
+
+
+def test_signed_bitfields():
+    class signed(cstruct):
+        _CFIELDS = {"a": field(i8, bits=3), "b": field(i8, bits=3, overflow=Overflow.wrap)}
+
+    value = signed()
+    value.a = -3
+    assert value.a == -3
+    assert bytes(value) == b"\x05"
+
+    with pytest.raises(ValueRangeError):
+        value.a = 4
+    with pytest.raises(ValueRangeError):
+        value.a = -5
+
+    value.b = 5
+    assert value.b == -3
+    assert value.a == -3
+
+
+def test_saturating_bitfields():
+    class saturated(cstruct):
+        _CFIELDS = {"a": field(u8, bits=3, overflow=Overflow.saturate)}
+
+    value = saturated()
+    value.a = 100
+    assert value.a == 7
+    value.a = -1
+    assert value.a == 0
+
+
+def test_invalid_bitfields():
+    for declared in [field(f32, bits=3), field(u8, bits=9), field(u8, bits=0)]:
+
+        class invalid(cstruct):
+            _CFIELDS = {"a": declared}
+
+        with pytest.raises(LayoutError):
+            invalid.__layout__()
+
+    with pytest.raises(LayoutError):
+        header.dtype()
+
+
+def test_declare_bitfields():
+    class flags:
+        version: Annotated[cerialize.u8, cerialize.bits(4)]
+        ihl: Annotated[cerialize.u8, cerialize.bits(4)]
+
+    declared = cerialize.cstruct(flags, bit_order=cerialize.bit_order.msb_first)
+    value = declared.from_buffer(b"\x45")
+    assert value.version == 4
+    assert value.ihl == 5
@@ -0,0 +1,48 @@
+import struct
+
+import pytest
+from _cerialize import cstruct, boolean, i8, u16, u32, f32, f64, LittleEndian
+
+
+class record(cstruct):
+    _CFIELDS = {"kind": u16, "length": u32}
+
+
+def test_bytes_of_primitives():
+    assert bytes(boolean(True)) == b"\x01"
+    assert bytes(i8(-1)) == b"\xff"
+    assert bytes(u16(0x0102, LittleEndian)) == b"\x02\x01"
+    assert len(bytes(f64(1.0))) == 8
+
+
+def test_bytes_of_struct():
+    value = record.from_buffer(bytes(range(8)), LittleEndian)
+
+    assert bytes(value) == bytes(range(8))
+    assert bytes(value.length) == bytes([4, 5, 6, 7])
+
+
+def test_memoryview_of_struct():
+    value = record()
+    view = memoryview(value)
+
+    assert view.nbytes == record.__packed_size__()
+    assert view.readonly
+    assert view.tobytes() == bytes(value)
+
+
+def test_writes_through_writable_buffer():
+    value = record.from_buffer(bytes(8), LittleEndian)
+    struct.pack_into("<I", value, 4, 1234)
+
+    assert value.length == u32(1234)
+    with pytest.raises(TypeError):
+        memoryview(value)[4:8] = bytes(4)
+
+
+def test_buffer_of_nested_value():
+    value = record.from_buffer(bytes(8), LittleEndian)
+    struct.pack_into("<H", value.kind, 0, 2)
+
+    assert value.kind == u16(2)
+    assert memoryview(f32(1.0)).nbytes == 4
@@ -0,0 +1,95 @@
+import mmap
+
+import pytest
+from _cerialize import cstruct, u8, u16, u32, f32, LittleEndian
+
+
+class record(cstruct):
+    _CFIELDS = {"kind": u16, "length": u32}
+
+
+class wrapper(cstruct):
+    _CFIELDS = {"inner": record, "flag": u8}
+
+
+def test_primitives_from_bytes_like():
+    assert u16(bytearray(b"\x02\x01"), LittleEndian) == u16(0x0102)
+    assert u16(memoryview(b"\x02\x01"), LittleEndian) == u16(0x0102)
+    assert f32(memoryview(bytes(f32(1.5)))) == f32(1.5)
+
+
+def test_from_buffer_accepts_bytes_like():
+    data = bytes(range(8))
+
+    for buffer in (data, bytearray(data), memoryview(data)):
+        assert bytes(record.from_buffer(buffer, LittleEndian)) == data
+
+
+def test_struct_from_bytes_like():
+    data = bytes(range(8))
+
+    for buffer in (data, bytearray(data), memoryview(data)):
+        assert bytes(record(buffer, LittleEndian)) == data
+    assert record(data, endianness=LittleEndian).kind == u16(0x0100)
+
+    shared = bytearray(8)
+    record(shared, LittleEndian).length = 1
+    assert shared[4] == 1
+
+    with pytest.raises(TypeError):
+        record(1)
+    with pytest.raises(TypeError):
+        record(data, LittleEndian, 3)
+    with pytest.raises(TypeError):
+        record(kind=1)
+
+
+def test_from_buffer_shares_writable_memory():
+    data = bytearray(8)
+    value = record.from_buffer(data, LittleEndian)
+    value.length = 1234
+
+    assert data[4:8] == (1234).to_bytes(4, "little")
+
+    data[0:2] = b"\x07\x00"
+    assert value.kind == u16(7)
+
+
+def test_from_buffer_shares_writable_memoryview_slice():
+    data = bytearray(12)
+    value = record.from_buffer(memoryview(data)[2:], LittleEndian)
+    value.kind = 0x0101
+
+    assert data[:4] == b"\x00\x00\x01\x01"
+
+
+def test_from_buffer_copies_readonly_memory():
+    data = bytes(8)
+    value = record.from_buffer(data, LittleEndian)
+    value.length = 1
+
+    assert data == bytes(8)
+    assert value.length == u32(1)
+
+
+def test_from_buffer_over_mmap():
+    with mmap.mmap(-1, 16) as memory:
+        value = record.from_buffer(memory, LittleEndian)
+        value.length = 0xAABBCCDD
+        nested = wrapper.from_buffer(memory, LittleEndian)
+
+        assert memory[4:8] == b"\xdd\xcc\xbb\xaa"
+        assert nested.inner.length == u32(0xAABBCCDD)
+        del value, nested
+
+
+def test_assign_bytes_like_to_field():
+    value = wrapper()
+    value.inner = bytearray(range(8))
+
+    assert bytes(value.inner) == bytes(range(8))
+
+
+def test_from_buffer_rejects_non_bytes_like():
+    with pytest.raises(TypeError):
+        record.from_buffer([0] * 8)
@@ -0,0 +1,69 @@
+import pytest
+from _cerialize import cstruct, field, u8, u16, u32, BigEndian, LittleEndian
+from cerialize.errors import BufferSizeError, LayoutError
+
+
+class header(cstruct):
+    _CFIELDS = {"kind": u8, "checksum": field(u16, endianness=BigEndian)}
+    _PACKED = True
+
+
+class sample(cstruct):
+    _CFIELDS = {"timestamp": u32, "header": header, "values": u8[2]}
+    _PACKED = True
+    _ENDIANNESS = LittleEndian
+
+
+def records(*values):
+    return b"".join(
+        timestamp.to_bytes(4, "little") + bytes([kind]) + checksum.to_bytes(2, "big") + bytes(pair)
+        for timestamp, kind, checksum, pair in values
+    )
+
+
+BUFFER = records((1, 2, 0x0304, (5, 6)), (0x01020304, 7, 0x0809, (10, 11)))
+
+
+def test_extract_column():
+    column = sample.extract_column(BUFFER, "timestamp")
+
+    assert column.shape == (2,)
+    assert list(column) == [u32(1), u32(0x01020304)]
+    assert bytes(column) == (1).to_bytes(4, "little") + (0x01020304).to_bytes(4, "little")
+
+
+def test_nested_fields_in_their_own_byte_order():
+    checksums = sample.extract_column(BUFFER, "header.checksum")
+
+    assert list(checksums) == [u16(0x0304), u16(0x0809)]
+    assert list(sample.extract_column(BUFFER, "header.kind")) == [u8(2), u8(7)]
+
+
+def test_array_fields():
+    column = sample.extract_column(bytearray(BUFFER), "values")
+
+    assert column.shape == (2, 2)
+    assert column[1, 0] == u8(10)
+
+
+def test_struct_byte_order_override():
+    column = sample.extract_column(BUFFER, "timestamp", BigEndian)
+
+    assert list(column) == [u32(0x01000000), u32(0x04030201)]
+
+
+def test_invalid_columns():
+    with pytest.raises(AttributeError):
+        sample.extract_column(BUFFER, "header.missing")
+    with pytest.raises(AttributeError):
+        sample.extract_column(BUFFER, "timestamp.value")
+    with pytest.raises(LayoutError):
+        sample.extract_column(BUFFER, "header")
+
+
+def test_trailing_bytes():
+    with pytest.raises(BufferSizeError):
+        sample.extract_column(BUFFER + b"\x00", "timestamp")
+
+    assert len(sample.extract_column(BUFFER + b"\x00", "timestamp", strict=False)) == 2
+    assert len(sample.extract_column(b"", "timestamp")) == 0
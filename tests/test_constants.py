@@ -0,0 +1,85 @@
+import pytest
+from typing import Annotated
+import cerialize
+from _cerialize import cstruct, field, cstr, cstring, Termination, u8, u16, u32, BigEndian, LittleEndian
+from cerialize.errors import ConstantMismatchError, LayoutError
+
+
+class elf_ident(cstruct):
+    _CFIELDS = {"magic": field(cstr[4, "latin-1", Termination.padded], constant="\x7fELF"), "klass": u8}
+    _PACKED = True
+
+
+class class_file(cstruct):
+    _CFIELDS = {"magic": field(u32, constant=0xCAFEBABE), "minor": u16, "major": u16}
+    _ENDIANNESS = BigEndian
+
+
+class container(cstruct):
+    _CFIELDS = {"count": u8, "file": class_file}
+    _ENDIANNESS = BigEndian
+
+
+def test_decode_verifies_constants():
+    value = class_file.from_buffer(b"\xca\xfe\xba\xbe\x00\x00\x00\x34")
+    assert value.magic == u32(0xCAFEBABE)
+    assert value.major == u16(0x34)
+
+    with pytest.raises(ConstantMismatchError, match="magic"):
+        class_file.from_buffer(b"\xbe\xba\xfe\xca\x00\x00\x00\x34")
+    with pytest.raises(ConstantMismatchError):
+        class_file.from_buffer(b"\xca\xfe\xba\xbe\x00\x00\x00\x34", LittleEndian)
+    assert elf_ident.from_buffer(b"\x7fELF\x02").klass == u8(2)
+    with pytest.raises(ConstantMismatchError):
+        elf_ident.from_buffer(b"MZ\x90\x00\x02")
+    with pytest.raises(ConstantMismatchError):
+        class_file(bytes(8))
+
+
+def test_nested_constants():
+    assert container().__bytes__() == b"\x00\x00\x00\x00\xca\xfe\xba\xbe\x00\x00\x00\x00"
+    with pytest.raises(ConstantMismatchError):
+        container.from_buffer(bytes(12))
+
+    records = class_file.from_buffer_many(b"\xca\xfe\xba\xbe\x00\x00\x00\x34" + bytes(8))
+    assert records[0].major == u16(0x34)
+    with pytest.raises(ConstantMismatchError):
+        records[1]
+
+
+def test_new_instances_hold_constants():
+    assert class_file().__bytes__() == b"\xca\xfe\xba\xbe\x00\x00\x00\x00"
+    assert elf_ident().__bytes__() == b"\x7fELF\x00"
+
+    value = class_file()
+    value.magic = u32(0xCAFEBABE)
+    with pytest.raises(ConstantMismatchError):
+        value.magic = u32(0)
+
+
+def test_invalid_constants():
+    class too_large(cstruct):
+        _CFIELDS = {"magic": field(u8, constant=0x1FF)}
+
+    with pytest.raises(LayoutError):
+        too_large.__layout__()
+
+    class dynamic(cstruct):
+        _CFIELDS = {"name": field(cstring, constant="name")}
+
+    with pytest.raises(LayoutError):
+        dynamic.__layout__()
+
+
+def test_declare_constants():
+    @cerialize.cstruct(endianness=cerialize.endianness.big)
+    class header:
+        magic: Annotated[cerialize.u32, cerialize.constant(0xCAFEBABE)]
+        version: cerialize.u16
+
+    value = header(cerialize.u16(3))
+    assert value.magic == cerialize.u32(0xCAFEBABE)
+    assert value.__bytes__() == b"\xca\xfe\xba\xbe\x00\x03\x00\x00"
+    assert repr(value) == "header(magic=u32(3405691582), version=u16(3))"
+    with pytest.raises(ConstantMismatchError):
+        header.from_buffer(bytes(8))
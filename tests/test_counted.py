@@ -0,0 +1,103 @@
+import pytest
+from _cerialize import cstruct, f32, u8, u16, u32, u64, BigEndian
+from cerialize.errors import BufferSizeError, LayoutError, ShapeError
+
+
+class item(cstruct):
+    _CFIELDS = {"id": u8, "flags": u8}
+
+
+class message(cstruct):
+    _CFIELDS = {"count": u16, "items": item["count"], "end": u8}
+    _ENDIANNESS = BigEndian
+    _PACKED = True
+
+
+def test_counted_types():
+    assert u16["count"] is u16["count"]
+    assert u16["count"] is not u16["length"]
+    assert message.__layout__().dynamic
+
+    with pytest.raises(ShapeError):
+        u16["count"].__packed_size__()
+
+
+def test_decode():
+    value = message.from_buffer(b"\x00\x02\x01\x02\x03\x04\x05")
+
+    assert value.count == u16(2)
+    assert len(value.items) == 2
+    assert value.items[1].id == u8(3)
+    assert value.end == u8(5)
+    assert bytes(value) == b"\x00\x02\x01\x02\x03\x04\x05"
+
+    empty = message.from_buffer(b"\x00\x00\x07")
+    assert len(empty.items) == 0
+    assert empty.end == u8(7)
+
+
+def test_truncated_buffer():
+    with pytest.raises(BufferSizeError):
+        message.from_buffer(b"\x00\x03\x01\x02\x03\x04\x05")
+
+
+def test_oversized_count():
+    class huge(cstruct):
+        _CFIELDS = {"count": u64, "items": u32["count"]}
+        _ENDIANNESS = BigEndian
+
+    with pytest.raises(BufferSizeError):
+        huge.from_buffer(b"\x40" + bytes(7))
+    with pytest.raises(BufferSizeError):
+        huge.from_buffer(b"\xff" * 8)
+    with pytest.raises(BufferSizeError):
+        huge.from_buffer(b"\x00" * 7 + b"\x02" + bytes(4))
+
+
+def test_assign_updates_count():
+    value = message.from_buffer(b"\x00\x00\x07")
+
+    value.items = [item.from_buffer(b"\x01\x02"), item.from_buffer(b"\x03\x04"), item.from_buffer(b"\x05\x06")]
+    assert value.count == u16(3)
+    assert value.end == u8(7)
+    assert bytes(value) == b"\x00\x03\x01\x02\x03\x04\x05\x06\x07"
+
+    value.items = b"\x09\x0a"
+    assert value.count == u16(1)
+    assert bytes(value) == b"\x00\x01\x09\x0a\x07"
+
+    with pytest.raises(BufferSizeError):
+        value.items = b"\x09"
+
+
+def test_assign_count_resizes():
+    value = message.from_buffer(b"\x00\x02\x01\x02\x03\x04\x05")
+
+    value.count = 1
+    assert bytes(value) == b"\x00\x01\x01\x02\x05"
+
+    value.count = 2
+    assert bytes(value) == b"\x00\x02\x01\x02\x00\x00\x05"
+
+
+def test_element_assignment_in_place():
+    value = message.from_buffer(b"\x00\x02\x01\x02\x03\x04\x05")
+
+    value.items[0].id = 9
+    assert bytes(value) == b"\x00\x02\x09\x02\x03\x04\x05"
+
+
+def test_invalid_count_field():
+    with pytest.raises(LayoutError):
+
+        class undeclared(cstruct):
+            _CFIELDS = {"items": u8["count"], "count": u8}
+
+        undeclared.__layout__()
+
+    with pytest.raises(LayoutError):
+
+        class not_an_integer(cstruct):
+            _CFIELDS = {"count": f32, "items": u8["count"]}
+
+        not_an_integer.__layout__()
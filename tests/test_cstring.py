@@ -0,0 +1,134 @@
+import pytest
+import cerialize
+from _cerialize import cstring, cstruct, u8, u16
+from cerialize.errors import BufferSizeError, LayoutError, ValueRangeError
+
+
+class entry(cstruct):
+    _CFIELDS = {"kind": u8, "name": cstring, "value": u16}
+
+
+class table(cstruct):
+    _CFIELDS = {"first": entry, "label": cstring, "count": u16}
+
+
+def test_cstring_values():
+    assert cstring("abc") == "abc"
+    assert bytes(cstring("abc")) == b"abc\x00"
+    assert bytes(cstring()) == b"\x00"
+    assert bytes(cstring["utf-8"]("é")) == b"\xc3\xa9\x00"
+
+    with pytest.raises(ValueRangeError):
+        cstring("a\x00b")
+    with pytest.raises(BufferSizeError):
+        cstring(b"ab")
+    with pytest.raises(LayoutError):
+        cstring.__packed_size__()
+
+
+def test_dynamic_layout():
+    layout = entry.__layout__()
+
+    assert layout.dynamic
+    assert layout.size == 4
+    with pytest.raises(LayoutError):
+        entry.__packed_size__()
+
+
+def test_offsets_follow_the_contents():
+    value = entry.from_buffer(b"\x07abc\x00\x00\x05\x00\xff")
+
+    assert value.kind == u8(7)
+    assert value.name == "abc"
+    assert value.value == u16(5)
+    assert value._offset_of("value") == 6
+    assert bytes(value) == b"\x07abc\x00\x00\x05\x00"
+
+    with pytest.raises(BufferSizeError):
+        entry.from_buffer(b"\x07abc")
+
+
+def test_resizing_moves_later_fields():
+    value = entry()
+    value.value = 0x0102
+
+    value.name = "hello"
+    assert value.value == u16(0x0102)
+    assert value._offset_of("value") == 8
+
+    value.name = "hi"
+    assert value.value == u16(0x0102)
+    assert len(bytes(value)) == 6
+
+
+def test_assign_packed_cstring():
+    value = entry()
+    value.name = b"abc\x00"
+    assert value.name == "abc"
+
+    with pytest.raises(BufferSizeError):
+        value.name = b"abc"
+    with pytest.raises(BufferSizeError):
+        value.name = b"a\x00b\x00"
+    assert value.name == "abc"
+
+
+def test_nested_dynamic_structs():
+    value = table.from_buffer(bytearray(b"\x01ab\x00\x02\x00" + b"cd\x00\x00" + b"\x03\x00"))
+
+    assert value.first.name == "ab"
+    assert value.label == "cd"
+    assert value.count == u16(3)
+
+    replacement = entry()
+    replacement.name = "longer"
+    value.first = replacement
+    assert value.first.name == "longer"
+    assert value.label == "cd"
+    assert value.count == u16(3)
+
+
+def test_resize_through_parent():
+    class outer(cstruct):
+        _CFIELDS = {"inner": table, "end": u8}
+
+    value = outer.from_buffer(b"\x01ab\x00\x02\x00" + b"cd\x00\x00" + b"\x03\x00" + b"\x09\x00")
+    value.inner.first.name = "longer"
+
+    assert value.inner.first.name == "longer"
+    assert value.inner.first.value == u16(2)
+    assert value.inner.label == "cd"
+    assert value.end == u8(9)
+
+    first = value.inner.first
+    first.name = "x"
+    first.value = 5
+    assert value.inner.first.name == "x"
+    assert value.inner.first.value == u16(5)
+    assert value.end == u8(9)
+
+
+def test_nested_declared_dynamic_struct():
+    @cerialize.cstruct
+    class person:
+        age: cerialize.u8
+        name: cstring
+
+    @cerialize.cstruct
+    class pair:
+        first: person
+        second: person
+
+    assert pair.__layout__().size == 4
+    value = pair.from_buffer(b"\x01ab\x00\x02cd\x00")
+    assert value.first.name == "ab"
+    assert value.second.age == u8(2)
+
+
+def test_exported_buffers_outlive_a_resize():
+    value = entry.from_buffer(b"\x07ab\x00\x05\x00")
+    view = memoryview(value)
+
+    value.name = "x" * 1000
+    del value
+    assert view.tobytes() == b"\x07ab\x00\x05\x00"
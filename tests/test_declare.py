@@ -115,6 +115,17 @@ def test_declare_array_fields():
     cerialize.cstruct(f32_array)
     cerialize.cstruct(f64_array)
 
+def test_declared_arrays_are_arrays():
+    @cerialize.cstruct
+    class samples:
+        values: cerialize.u16[2, 3]
+
+    assert samples.__packed_size__() == 12
+    value = samples.from_buffer(bytes(range(12)))
+    assert len(value.values) == 2
+    assert isinstance(value.values[0][0], cerialize.u16)
+
+
 def test_declare_nested_field():
     class inner(Generic[*Shape]):
         _: cerialize.i8
@@ -143,3 +154,4 @@ def test_declare_empty_struct():
         pass
 
     cerialize.cstruct(empty)
+
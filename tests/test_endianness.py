@@ -0,0 +1,59 @@
+import cerialize
+from _cerialize import cstruct, field, Endianness, NativeEndian, LittleEndian, BigEndian, u16, u32
+
+
+def test_sentinels_are_endianness_members():
+    assert NativeEndian == Endianness.native
+    assert LittleEndian == Endianness.little
+    assert BigEndian == Endianness.big
+    assert isinstance(BigEndian, Endianness)
+    assert cerialize.endianness is Endianness
+
+
+def test_declared_endianness_is_the_default():
+    @cerialize.cstruct(endianness=cerialize.endianness.big)
+    class header:
+        length: cerialize.u16
+        checksum: cerialize.u16
+
+    assert header._ENDIANNESS == Endianness.big
+    assert bytes(header(0x0102, 0x0304)) == b"\x01\x02\x03\x04"
+    assert header.from_buffer(b"\x00\x05\x00\x06").length == u16(5)
+
+
+def test_declared_types_take_an_endianness():
+    assert bytes(cerialize.u16(0x0102, cerialize.endianness.big)) == b"\x01\x02"
+    assert bytes(cerialize.i32(-2, endianness=cerialize.endianness.little)) == b"\xfe\xff\xff\xff"
+    assert bytes(cerialize.f32(1.0, cerialize.endianness.big)) == b"\x3f\x80\x00\x00"
+
+
+def test_from_buffer_overrides_declared_endianness():
+    @cerialize.cstruct(endianness=cerialize.endianness.little)
+    class header:
+        length: cerialize.u16
+
+    assert header.from_buffer(b"\x00\x05", BigEndian).length == u16(5)
+
+
+def test_native_is_the_default():
+    @cerialize.cstruct
+    class header:
+        length: cerialize.u32
+
+    assert header._ENDIANNESS == Endianness.native
+    assert bytes(header(1)) == bytes(u32(1))
+
+
+def test_nested_struct_keeps_declared_endianness():
+    class inner(cstruct):
+        _CFIELDS = {"value": u16}
+        _ENDIANNESS = BigEndian
+
+    class outer(cstruct):
+        _CFIELDS = {"first": u16, "inner": inner, "last": field(inner, endianness=LittleEndian)}
+        _ENDIANNESS = LittleEndian
+
+    value = outer.from_buffer(b"\x01\x00\x00\x02\x03\x00")
+    assert value.first == u16(1)
+    assert value.inner.value == u16(2)
+    assert value.last.value == u16(3)
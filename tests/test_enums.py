@@ -0,0 +1,85 @@
+import enum
+
+import pytest
+from _cerialize import cstruct, cenum, u8, u16, UnknownValue, BigEndian
+from cerialize.errors import LayoutError, ValueRangeError
+
+
+class Status(enum.IntEnum):
+    idle = 0
+    busy = 1
+    failed = 7
+
+
+class device(cstruct):
+    _CFIELDS = {"status": cenum[u8, Status], "mode": cenum[u16, {"off": 0, "on": 1}, UnknownValue.keep]}
+    _ENDIANNESS = BigEndian
+
+
+def test_read_members():
+    value = device.from_buffer(b"\x01\x00\x00\x01")
+    assert value.status is Status.busy
+    assert value.mode.name == "on"
+    assert value.mode == 1
+
+
+def test_write_members_names_and_integers():
+    value = device.from_buffer(b"\x00\x00\x00\x00")
+    value.status = Status.failed
+    assert value.status is Status.failed
+    value.status = "busy"
+    assert value.status is Status.busy
+    value.status = 0
+    assert value.status is Status.idle
+    assert value.__bytes__() == b"\x00\x00\x00\x00"
+
+
+def test_unknown_values():
+    value = device.from_buffer(b"\x05\x00\x00\x09")
+    with pytest.raises(ValueRangeError):
+        value.status
+    assert value.mode == 9
+    assert type(value.mode) is int
+
+    with pytest.raises(ValueRangeError):
+        value.status = 5
+    with pytest.raises(ValueRangeError):
+        value.status = "unknown"
+    value.mode = 300
+    assert value.mode == 300
+
+
+def test_standalone_values():
+    status = cenum[u8, Status]
+    assert status("failed").value is Status.failed
+    assert status(b"\x01").value is Status.busy
+    assert status().value is Status.idle
+    assert status(Status.busy) == status(1)
+    assert repr(status(1)) == "cenum[u8, Status](Status.busy)"
+    assert status.__packed_size__() == 1
+
+
+def test_enum_types_are_cached():
+    assert cenum[u8, Status] is cenum[u8, Status]
+    assert cenum[u8, {"off": 0, "on": 1}] is cenum[u8, {"off": 0, "on": 1}]
+    assert cenum[u8, Status] is not cenum[u8, Status, UnknownValue.keep]
+    assert cenum[u8, {"off": 0, "on": 1}] is not cenum[u8, {"off": 0, "on": 2}]
+
+    class Other(enum.IntEnum):
+        idle = 0
+
+    Other.__name__ = "Status"
+    assert cenum[u8, Status] is not cenum[u8, Other]
+
+
+def test_layout():
+    assert device.__layout__().size == 4
+    assert cenum[u16, Status].__alignment__() == 2
+    with pytest.raises(LayoutError):
+        cenum[u8]
+    with pytest.raises(LayoutError):
+        cenum[int, Status]
+    with pytest.raises(LayoutError):
+        cenum[u8, [0, 1]]
+    with pytest.raises(LayoutError):
+        cenum()
@@ -0,0 +1,94 @@
+import pytest
+from _cerialize import cstruct, boolean, i8, u8, u16, u32, f32, LittleEndian
+from _cerialize.errors import (
+    CerializeError,
+    BufferSizeError,
+    LayoutError,
+    ValueRangeError,
+    ShapeError,
+)
+
+
+class record(cstruct):
+    _CFIELDS = {"kind": u16, "samples": u8[3]}
+
+
+def test_hierarchy():
+    for error in (BufferSizeError, LayoutError, ValueRangeError, ShapeError):
+        assert issubclass(error, CerializeError)
+
+    assert issubclass(BufferSizeError, ValueError)
+    assert issubclass(LayoutError, TypeError)
+    assert issubclass(ValueRangeError, OverflowError)
+    assert issubclass(ShapeError, ValueError)
+    assert BufferSizeError.__module__ == "cerialize.errors"
+
+
+def test_wrong_buffer_size():
+    with pytest.raises(BufferSizeError, match="u16 requires a buffer of exactly 2 bytes, got 3"):
+        u16(b"abc")
+
+    with pytest.raises(BufferSizeError, match="record requires a buffer of at least 6 bytes, got 2"):
+        record.from_buffer(b"ab")
+
+    value = record()
+    with pytest.raises(BufferSizeError, match="The field kind of record is 2 bytes large, got 1 bytes"):
+        value.kind = b"a"
+
+
+def test_out_of_range():
+    with pytest.raises(ValueRangeError, match=r"256 is out of range for u8 \(0..=255\)"):
+        u8(256)
+
+    with pytest.raises(ValueRangeError, match=r"-129 is out of range for i8"):
+        i8(-129)
+
+    with pytest.raises(OverflowError):
+        record().kind = -1
+
+
+def test_unsupported_value():
+    with pytest.raises(TypeError, match="u32 can't be created from a value of type list"):
+        u32([1, 2])
+
+    with pytest.raises(TypeError):
+        f32("1.0")
+
+    with pytest.raises(TypeError):
+        boolean([1])
+
+
+def test_invalid_shape():
+    with pytest.raises(ShapeError, match="non-negative integers"):
+        u8[1.5]
+
+    with pytest.raises(ShapeError):
+        u8[2, -1]
+
+    with pytest.raises(ShapeError, match="Expected 3 elements for an array of u8, got 2"):
+        record().samples = [1, 2]
+
+
+def test_invalid_layout():
+    class untyped(cstruct):
+        _CFIELDS = {"a": int}
+
+    class undeclared(cstruct):
+        pass
+
+    with pytest.raises(LayoutError, match="The field a of .*untyped"):
+        untyped.__layout__()
+
+    with pytest.raises(LayoutError, match="_CFIELDS"):
+        undeclared.__packed_size__()
+
+
+def test_array_types_are_cached_per_class():
+    class first(cstruct):
+        _CFIELDS = {"a": u8}
+
+    second = type("first", (cstruct,), {"_CFIELDS": {"a": u16}})
+
+    assert first[2] is first[2]
+    assert first[2] is not second[2]
+    assert second[2].__packed_size__() == 4
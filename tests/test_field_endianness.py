@@ -0,0 +1,51 @@
+from _cerialize import cstruct, field, u16, u32, BigEndian, LittleEndian, NativeEndian
+
+
+class ip_header(cstruct):
+    _CFIELDS = {
+        "length": field(u16, endianness=BigEndian),
+        "checksum": field(u16, endianness=BigEndian),
+    }
+
+
+class log_record(cstruct):
+    _CFIELDS = {
+        "timestamp": u32,
+        "header": ip_header,
+        "counters": field(u16[2], endianness=BigEndian),
+    }
+
+
+def test_field_overrides_struct_endianness():
+    data = bytes([1, 0, 0, 0, 0, 20, 0xAB, 0xCD, 0, 1, 0, 2])
+    record = log_record.from_buffer(data, LittleEndian)
+
+    assert record.timestamp == u32(1)
+    assert record.header.length == u16(20)
+    assert record.header.checksum == u16(0xABCD)
+    assert bytes(record.counters) == b"\x00\x01\x00\x02"
+
+
+def test_assignment_uses_field_endianness():
+    record = log_record.from_buffer(bytes(12), LittleEndian)
+    record.timestamp = 2
+    record.header.length = 0x0102
+    record.counters = [3, 4]
+
+    assert bytes(record) == bytes([2, 0, 0, 0, 1, 2, 0, 0, 0, 3, 0, 4])
+
+
+def test_assigning_a_value_with_another_byte_order():
+    record = log_record.from_buffer(bytes(12), LittleEndian)
+    record.header.checksum = u16(0x0102, LittleEndian)
+
+    assert bytes(record.header) == b"\x00\x00\x01\x02"
+
+
+def test_field_options():
+    declared = field(u16, endianness=BigEndian)
+
+    assert declared.endianness == BigEndian
+    assert field(u16).endianness is None
+    assert repr(declared) == "field(u16, endianness=Endianness.big)"
+    assert field(u16, endianness=NativeEndian).endianness == NativeEndian
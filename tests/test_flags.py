@@ -0,0 +1,91 @@
+import enum
+
+import pytest
+import cerialize
+from _cerialize import cstruct, cflags, u8, u16, i8, BigEndian
+from cerialize.errors import LayoutError, ValueRangeError
+
+
+class Permissions(enum.IntFlag):
+    read = 1
+    write = 2
+    execute = 4
+
+
+class entry(cstruct):
+    _CFIELDS = {"mode": cflags[u8, Permissions], "options": cflags[u16, {"hidden": 0x100, "system": 0x200}]}
+    _ENDIANNESS = BigEndian
+
+
+def test_read_flags():
+    value = entry.from_buffer(b"\x13\x00\x03\x00")
+    assert Permissions.read in value.mode
+    assert "write" in value.mode
+    assert "execute" not in value.mode
+    assert list(value.mode) == [Permissions.read, Permissions.write]
+    assert len(value.mode) == 2
+    assert value.mode.residual == 0x10
+    assert value.mode.flags == Permissions(0x13)
+    assert value.mode == 0x13
+    assert [flag.name for flag in value.options] == ["hidden", "system"]
+
+
+def test_write_flags():
+    value = entry.from_buffer(b"\x00\x00\x00\x00")
+    value.mode = ["read", "execute"]
+    assert value.mode == {"read", "execute"}
+    value.mode = Permissions.write
+    assert value.mode == Permissions.write
+    value.mode = 0x21
+    assert value.mode.residual == 0x20
+    value.options = {"system"}
+    assert value.__bytes__() == b"\x21\x00\x02\x00"
+
+    with pytest.raises(ValueRangeError):
+        value.mode = ["read", "delete"]
+
+
+def test_modify_in_place():
+    value = entry.from_buffer(b"\x01\x00\x00\x00")
+    value.mode.add("write")
+    value.mode.discard(Permissions.read)
+    assert value.__bytes__() == b"\x02\x00\x00\x00"
+
+    combined = value.mode | "execute"
+    assert combined == {"write", "execute"}
+    assert value.mode == {"write"}
+    assert (combined & ["execute", "read"]) == Permissions.execute
+    assert not (combined - combined)
+
+
+def test_repr():
+    mode = cflags[u8, Permissions]
+    assert str(mode(["read", "write"])) == "read|write"
+    assert str(mode(0x11)) == "read|0x10"
+    assert str(mode()) == "0"
+    assert repr(mode(Permissions.execute)) == "cflags[u8, Permissions](execute)"
+
+    @cerialize.cstruct
+    class header:
+        mode: cflags[cerialize.u8, Permissions]
+        size: cerialize.u8
+
+    assert repr(header(mode=["read", "write"], size=cerialize.u8(3))) == "header(mode=read|write, size=u8(3))"
+
+
+def test_flag_types_are_cached():
+    assert cflags[u8, Permissions] is cflags[u8, Permissions]
+    assert cflags[u16, {"hidden": 0x100}] is cflags[u16, {"hidden": 0x100}]
+    assert cflags[u16, {"hidden": 0x100}] is not cflags[u16, {"hidden": 0x200}]
+    assert cflags[u8, Permissions] is not cflags[u16, Permissions]
+
+
+def test_layout():
+    assert entry.__layout__().size == 4
+    assert cflags[u16, Permissions].__alignment__() == 2
+    with pytest.raises(LayoutError):
+        cflags[i8, Permissions]
+    with pytest.raises(LayoutError):
+        cflags[u8, enum.IntEnum("Status", {"idle": 0})]
+    with pytest.raises(LayoutError):
+        cflags()
@@ -0,0 +1,79 @@
+import pytest
+from _cerialize import cstruct, u8, u16, u32, u64, i128, LittleEndian
+from _cerialize.errors import LayoutError
+
+
+def test_layout_inserts_padding_between_fields():
+    class padded(cstruct):
+        _CFIELDS = {"a": u8, "b": u32}
+
+    assert padded.__packed_size__() == 8
+    assert padded.__alignment__() == 4
+
+
+def test_layout_inserts_tail_padding():
+    class tail(cstruct):
+        _CFIELDS = {"a": u32, "b": u8}
+
+    assert tail.__packed_size__() == 8
+
+
+def test_layout_aligns_nested_structs_and_arrays():
+    class inner(cstruct):
+        _CFIELDS = {"a": u8, "b": u64}
+
+    class outer(cstruct):
+        _CFIELDS = {"a": u8, "b": inner, "c": u16[3]}
+
+    assert inner.__alignment__() == 8
+    assert outer.__alignment__() == 8
+    assert outer.__packed_size__() == 8 + 16 + 6 + 2
+
+
+def test_layout_packed():
+    class packed(cstruct):
+        _CFIELDS = {"a": u8, "b": u32, "c": i128}
+        _PACKED = True
+
+    assert packed.__packed_size__() == 1 + 4 + 16
+    assert packed.__alignment__() == 1
+
+
+def test_layout_explicit_alignment():
+    class aligned(cstruct):
+        _CFIELDS = {"a": u8}
+        _ALIGNMENT = 16
+
+    assert aligned.__alignment__() == 16
+    assert aligned.__packed_size__() == 16
+
+
+def test_layout_invalid_alignment():
+    class misaligned(cstruct):
+        _CFIELDS = {"a": u8}
+        _ALIGNMENT = 3
+
+    with pytest.raises(LayoutError, match="power of two"):
+        misaligned.__packed_size__()
+
+
+def test_layout_is_compiled_once_per_class():
+    class cached(cstruct):
+        _CFIELDS = {"a": u8, "b": u32}
+
+    layout = cached.__layout__()
+    assert cached.__layout__() is layout
+    assert layout.fields == [("a", u8, 0, 1), ("b", u32, 4, 4)]
+
+
+def test_layout_field_lookup():
+    class lookup(cstruct):
+        _CFIELDS = {"a": u8, "b": u16, "c": u32}
+
+    value = lookup.from_buffer(bytes([1, 0, 2, 0, 3, 0, 0, 0]), LittleEndian)
+    assert value._offset_of("c") == 4
+    assert value._type_of("b") is u16
+    assert value.c == u32(3)
+
+    with pytest.raises(AttributeError):
+        value.d
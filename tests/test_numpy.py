@@ -0,0 +1,60 @@
+import pytest
+from _cerialize import cstruct, field, boolean, i8, u8, u16, u32, f32, i128, BigEndian, LittleEndian
+
+numpy = pytest.importorskip("numpy")
+
+
+class inner(cstruct):
+    _CFIELDS = {"flag": u8, "checksum": field(u16, endianness=BigEndian)}
+
+
+class record(cstruct):
+    _CFIELDS = {"timestamp": u32, "header": inner, "samples": f32[2]}
+    _ENDIANNESS = LittleEndian
+
+
+def test_primitive_dtypes():
+    assert u16.dtype() == numpy.dtype("=u2")
+    assert u16.dtype(BigEndian) == numpy.dtype(">u2")
+    assert i8.dtype() == numpy.dtype("i1")
+    assert f32.dtype(LittleEndian) == numpy.dtype("<f4")
+    assert boolean.dtype() == numpy.dtype("?")
+
+    with pytest.raises(TypeError):
+        i128.dtype()
+
+
+def test_array_dtype():
+    assert u8[2, 3].dtype() == numpy.dtype(("u1", (2, 3)))
+
+
+def test_struct_dtype():
+    dtype = record.dtype()
+
+    assert dtype.itemsize == record.__packed_size__()
+    assert dtype.names == ("timestamp", "header", "samples")
+    assert dtype.fields["timestamp"] == (numpy.dtype("<u4"), 0)
+    assert dtype.fields["header"][0].fields["checksum"] == (numpy.dtype(">u2"), 2)
+    assert dtype.fields["samples"] == (numpy.dtype(("<f4", (2,))), 8)
+
+
+def test_struct_as_numpy_is_a_view():
+    value = record()
+    array = value.as_numpy()
+    array["timestamp"] = 7
+
+    assert array.shape == ()
+    assert value.timestamp == u32(7)
+
+
+def test_array_as_numpy():
+    records = record[3]()
+    records[2].header.checksum = 0x0102
+    array = records.as_numpy()
+
+    assert array.shape == (3,)
+    assert list(array["header"]["checksum"]) == [0, 0, 0x0102]
+
+    matrix = u8[2, 3](bytes(range(6))).as_numpy()
+    assert matrix.shape == (2, 3)
+    assert matrix[1, 2] == 5
@@ -0,0 +1,67 @@
+import pytest
+from _cerialize import cstruct, field, Overflow, i8, u8, i16, u32, i128, u128, LittleEndian
+from _cerialize.errors import ValueRangeError
+
+
+class counters(cstruct):
+    _CFIELDS = {
+        "sequence": field(u8, overflow=Overflow.wrap),
+        "level": field(i8, overflow=Overflow.saturate),
+        "limit": u8,
+        "history": field(u8[2], overflow=Overflow.wrap),
+    }
+
+
+def test_error_is_the_default():
+    with pytest.raises(ValueRangeError):
+        u8(256)
+
+    with pytest.raises(OverflowError):
+        i16(-32769, overflow=Overflow.error)
+
+    assert u8(255) == u8(255)
+
+
+def test_wrap():
+    assert u8(256, overflow=Overflow.wrap) == u8(0)
+    assert u8(-1, overflow=Overflow.wrap) == u8(255)
+    assert i8(200, overflow=Overflow.wrap) == i8(-56)
+    assert u32(2**40 + 7, overflow=Overflow.wrap) == u32(7)
+    assert i128(2**127, overflow=Overflow.wrap) == i128(-(2**127))
+    assert u128(-1, overflow=Overflow.wrap) == u128(2**128 - 1)
+
+
+def test_saturate():
+    assert u8(1000, overflow=Overflow.saturate) == u8(255)
+    assert u8(-5, overflow=Overflow.saturate) == u8(0)
+    assert i8(-1000, overflow=Overflow.saturate) == i8(-128)
+    assert i8(1000, overflow=Overflow.saturate) == i8(127)
+
+
+def test_policy_only_applies_to_out_of_range_values():
+    for policy in (Overflow.error, Overflow.wrap, Overflow.saturate):
+        assert i8(-3, overflow=policy) == i8(-3)
+
+
+def test_field_policies():
+    value = counters()
+    value.sequence = 257
+    value.level = -1000
+    value.history = [256, 513]
+
+    assert value.sequence == u8(1)
+    assert value.level == i8(-128)
+    assert bytes(value.history) == b"\x00\x01"
+
+    with pytest.raises(ValueRangeError, match="u8"):
+        value.limit = 256
+
+
+def test_field_declaration():
+    declared = field(u8, overflow=Overflow.wrap)
+
+    assert declared.type is u8
+    assert declared.overflow == Overflow.wrap
+    assert field(u32).overflow is None
+    assert repr(declared) == "field(u8, overflow=Overflow.wrap)"
+    assert counters.__layout__().fields[0] == ("sequence", u8, 0, 1)
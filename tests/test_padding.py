@@ -0,0 +1,88 @@
+import pytest
+from typing import Annotated
+import cerialize
+from _cerialize import cstruct, field, pad, reserved, cstring, u8, u16, u32, BigEndian
+from cerialize.errors import LayoutError
+
+
+class header(cstruct):
+    _CFIELDS = {
+        "magic": u32,
+        "version": field(u16, offset=0x10),
+        "_reserved": reserved[2],
+        "flags": u8,
+        "_pad": pad[3],
+    }
+    _ENDIANNESS = BigEndian
+
+
+def test_explicit_offsets():
+    layout = header.__layout__()
+    assert [(name, offset) for name, _, offset, _ in layout.fields] == [
+        ("magic", 0),
+        ("version", 0x10),
+        ("_reserved", 0x12),
+        ("flags", 0x14),
+        ("_pad", 0x15),
+    ]
+    assert layout.size == 0x18
+
+    value = header.from_buffer(b"\x7fELF" + bytes(12) + b"\x00\x02\xaa\xbb\x05\xcc\xdd\xee")
+    assert value.version == u16(2)
+    assert value.flags == u8(5)
+
+
+def test_padding_is_hidden_and_kept():
+    buffer = b"\x7fELF" + bytes(12) + b"\x00\x02\xaa\xbb\x05\xcc\xdd\xee"
+    value = header.from_buffer(buffer)
+    with pytest.raises(AttributeError):
+        value._reserved
+    with pytest.raises(AttributeError):
+        value._pad = b"\x00\x00\x00"
+
+    value.flags = u8(6)
+    assert value.__bytes__() == buffer[:0x14] + b"\x06" + buffer[0x15:]
+
+
+def test_resizing_keeps_explicit_offsets():
+    class named(cstruct):
+        _CFIELDS = {"a": u8, "b": field(u16, offset=0x10), "name": cstring}
+        _ENDIANNESS = BigEndian
+
+    value = named.from_buffer(b"\x01" + bytes(15) + b"\x00\x02\x00\x00")
+    value.name = "hello"
+
+    assert value._offset_of("b") == 0x10
+    assert value.b == u16(2)
+    assert value.name == "hello"
+    assert value.__bytes__() == b"\x01" + bytes(15) + b"\x00\x02hello\x00"
+
+
+def test_overlapping_offsets():
+    class overlapping(cstruct):
+        _CFIELDS = {"magic": u32, "version": field(u16, offset=2)}
+
+    with pytest.raises(LayoutError):
+        overlapping.__layout__()
+
+    class after_dynamic(cstruct):
+        _CFIELDS = {"name": cstring, "version": field(u16, offset=0x20)}
+
+    with pytest.raises(LayoutError):
+        after_dynamic.__layout__()
+
+    with pytest.raises(LayoutError):
+        pad[0]
+
+
+def test_declare_padding():
+    @cerialize.cstruct(endianness=cerialize.endianness.big)
+    class record:
+        kind: cerialize.u8
+        _pad: pad[3]
+        size: Annotated[cerialize.u16, cerialize.offset(8)]
+
+    assert record.__packed_size__() == 10
+    value = record(cerialize.u8(1), cerialize.u16(3))
+    assert repr(value) == "record(kind=u8(1), size=u16(3))"
+    assert value.__bytes__() == b"\x01" + bytes(7) + b"\x00\x03"
@@ -0,0 +1,86 @@
+import pytest
+from _cerialize import bytes_prefixed, pascal_string, cstruct, f32, i8, u8, u16, u32, u64, BigEndian
+from cerialize.errors import BufferSizeError, LayoutError, ValueRangeError
+
+
+class message(cstruct):
+    _CFIELDS = {"tag": u8, "body": bytes_prefixed[u16], "name": pascal_string[u8], "end": u8}
+    _ENDIANNESS = BigEndian
+
+
+def test_prefixed_types():
+    assert bytes_prefixed[u32] is bytes_prefixed[u32]
+    assert bytes_prefixed[u32].__alignment__() == 4
+    assert pascal_string[u16, "utf-8"] is not pascal_string[u16]
+
+    with pytest.raises(LayoutError):
+        bytes_prefixed[f32]
+    with pytest.raises(LayoutError):
+        bytes_prefixed[u32].__packed_size__()
+
+
+def test_bytes_prefixed():
+    value = bytes_prefixed[u32](b"abc")
+
+    assert value == b"abc"
+    assert value.data == b"abc"
+    assert len(value) == 3
+    assert bytes(value) == b"\x03\x00\x00\x00abc"
+    assert bytes(bytes_prefixed[u32](b"ab", BigEndian)) == b"\x00\x00\x00\x02ab"
+
+
+def test_pascal_string():
+    assert bytes(pascal_string[u8]("hi")) == b"\x02hi"
+    assert bytes(pascal_string[u8, "utf-8"]("é")) == b"\x02\xc3\xa9"
+    assert pascal_string[u8](b"\x02hi") == "hi"
+
+    with pytest.raises(ValueRangeError):
+        pascal_string[u8]("x" * 256)
+    with pytest.raises(BufferSizeError):
+        pascal_string[u8](b"\x05hi")
+
+
+def test_prefix_follows_the_payload():
+    value = message()
+    value.body = b"xyz"
+    value.name = "ok"
+    value.end = 9
+
+    assert bytes(value) == b"\x00\x00\x00\x03xyz\x02ok\x09\x00"
+    assert value.body == b"xyz"
+    assert value.name == "ok"
+    assert value._offset_of("end") == 10
+
+
+def test_assign_packed_pascal_string():
+    value = message()
+    value.name = b"\x02ok"
+    assert value.name == "ok"
+
+    with pytest.raises(BufferSizeError):
+        value.name = b"\x09ab"
+    with pytest.raises(BufferSizeError):
+        value.name = b"\x01ab"
+    assert bytes(value) == b"\x00\x00\x00\x00\x02ok\x00"
+
+
+def test_decoding():
+    value = message.from_buffer(b"\x01\x00\x00\x02hi\x03abc\x07\x00trailing")
+
+    assert value.tag == u8(1)
+    assert value.body == b"hi"
+    assert value.name == "abc"
+    assert value.end == u8(7)
+    assert len(bytes(value)) == 12
+
+    with pytest.raises(BufferSizeError):
+        message.from_buffer(b"\x01\x00\x00\x09hi")
+    with pytest.raises(BufferSizeError):
+        bytes_prefixed[i8].from_buffer(b"\xff")
+
+
+def test_oversized_prefix():
+    with pytest.raises(BufferSizeError, match=str(2**64 - 1)):
+        bytes_prefixed[u64].from_buffer(b"\xff" * 8)
+    with pytest.raises(BufferSizeError, match="length of 3"):
+        pascal_string[u8].from_buffer(b"\x03ab")
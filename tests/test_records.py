@@ -0,0 +1,63 @@
+import pytest
+from _cerialize import cstruct, u8, u16, BigEndian, LittleEndian
+from cerialize.errors import BufferSizeError
+
+
+class point(cstruct):
+    _CFIELDS = {"x": u8, "y": u16}
+    _PACKED = True
+    _ENDIANNESS = LittleEndian
+
+
+def packed(*points):
+    return b"".join(bytes([x, y & 0xFF, y >> 8]) for x, y in points)
+
+
+def test_from_buffer_many():
+    records = point.from_buffer_many(packed((1, 2), (3, 0x0405), (6, 7)))
+
+    assert len(records) == 3
+    assert records.type is point
+    assert records.remainder == b""
+    assert [(p.x, p.y) for p in records] == [(u8(1), u16(2)), (u8(3), u16(0x0405)), (u8(6), u16(7))]
+    assert records[-1].x == u8(6)
+
+    with pytest.raises(IndexError):
+        records[3]
+
+
+def test_slices():
+    records = point.from_buffer_many(packed((1, 2), (3, 4), (5, 6), (7, 8)))
+
+    assert [p.x for p in records[1:3]] == [u8(3), u8(5)]
+    assert len(records[4:]) == 0
+    assert [p.x for p in records[::2]] == [u8(1), u8(5)]
+
+
+def test_iter_unpack():
+    values = list(u16.iter_unpack(b"\x01\x00\x02\x00", LittleEndian))
+
+    assert values == [u16(1), u16(2)]
+    assert list(u16.iter_unpack(b"\x00\x01", BigEndian)) == [u16(1)]
+
+
+def test_records_are_views():
+    buffer = bytearray(packed((1, 2), (3, 4)))
+    records = point.from_buffer_many(buffer)
+    records[1].y = 0x0506
+
+    assert buffer[3:] == bytes([3, 6, 5])
+
+
+def test_trailing_bytes():
+    buffer = packed((1, 2), (3, 4)) + b"\xff"
+
+    with pytest.raises(BufferSizeError):
+        point.from_buffer_many(buffer)
+    with pytest.raises(BufferSizeError):
+        list(point.iter_unpack(buffer))
+
+    records = point.from_buffer_many(buffer, strict=False)
+    assert len(records) == 2
+    assert records.remainder == b"\xff"
+    assert len(list(point.iter_unpack(buffer, strict=False))) == 2
@@ -0,0 +1,73 @@
+import pytest
+from _cerialize import cstr, char, cstruct, u8, Encoding, Termination
+from cerialize.errors import BufferSizeError, LayoutError, ValueRangeError
+
+
+class record(cstruct):
+    _CFIELDS = {"id": u8, "name": char[6]}
+
+
+def test_string_types():
+    assert cstr[8] is cstr[8]
+    assert char[8] is cstr[8]
+    assert cstr[8, "utf-8"] is cstr[8, Encoding.utf8]
+    assert cstr[8] is not cstr[8, Termination.padded]
+    assert cstr[8].__packed_size__() == 8
+    assert cstr[8].__alignment__() == 1
+
+    with pytest.raises(LayoutError):
+        cstr.__packed_size__()
+    with pytest.raises(LayoutError):
+        cstr[8, "utf-16"]
+
+
+def test_null_padding():
+    value = cstr[6]("abc")
+
+    assert value == "abc"
+    assert str(value) == "abc"
+    assert bytes(value) == b"abc\x00\x00\x00"
+    assert cstr[6](b"ab\x00cd\x00") == "ab"
+
+
+def test_termination():
+    with pytest.raises(ValueRangeError):
+        cstr[3]("abc")
+    assert bytes(cstr[3, Termination.padded]("abc")) == b"abc"
+
+    with pytest.raises(ValueRangeError):
+        cstr[4, Termination.padded]("abcde")
+    with pytest.raises(ValueRangeError):
+        cstr[4]("a\x00b")
+    with pytest.raises(BufferSizeError):
+        cstr[4](b"ab")
+
+
+def test_encodings():
+    assert bytes(cstr[4, "utf-8"]("é")) == b"\xc3\xa9\x00\x00"
+    assert bytes(cstr[4, "latin-1"]("é")) == b"\xe9\x00\x00\x00"
+    assert cstr[4, "latin-1"](b"\xe9\x00\x00\x00") == "é"
+
+    with pytest.raises(UnicodeEncodeError):
+        cstr[4]("é")
+    with pytest.raises(UnicodeDecodeError):
+        str(cstr[4](b"\xe9\x00\x00\x00"))
+
+
+def test_struct_fields():
+    value = record()
+    value.name = "hey"
+
+    assert value.name == "hey"
+    assert isinstance(value.name, str)
+    assert bytes(value) == b"\x00hey\x00\x00\x00"
+
+    with pytest.raises(ValueRangeError):
+        value.name = "toolong"
+
+
+def test_arrays_of_strings():
+    names = cstr[4][2](["ab", "c"])
+
+    assert list(names) == ["ab", "c"]
+    assert bytes(names) == b"ab\x00\x00c\x00\x00\x00"
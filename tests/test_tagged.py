@@ -0,0 +1,126 @@
+import pytest
+import cerialize
+from _cerialize import cstruct, tagged, f32, u8, u16, u32, BigEndian
+from cerialize.errors import LayoutError, UnknownTagError
+
+
+class ping(cstruct):
+    _CFIELDS = {"sequence": u16}
+    _ENDIANNESS = BigEndian
+
+
+class reading(cstruct):
+    _CFIELDS = {"sensor": u8, "value": f32}
+    _ENDIANNESS = BigEndian
+    _PACKED = True
+
+
+class message(cstruct):
+    _CFIELDS = {"kind": u8, "payload": tagged["kind", {1: ping, 2: reading}]}
+    _ENDIANNESS = BigEndian
+    _PACKED = True
+
+
+class lenient(cstruct):
+    _CFIELDS = {"kind": u8, "payload": tagged["kind", {1: ping}, bytes]}
+    _PACKED = True
+
+
+def test_decode_variants():
+    value = message.from_buffer(b"\x01\x00\x07")
+    assert isinstance(value.payload, ping)
+    assert value.payload.sequence == u16(7)
+
+    value = message.from_buffer(b"\x02\x03\x3f\x80\x00\x00")
+    assert isinstance(value.payload, reading)
+    assert value.payload.sensor == u8(3)
+    assert value.payload.value == f32(1.0)
+
+
+def test_unknown_tag():
+    with pytest.raises(UnknownTagError, match="9 isn't the tag of any variant"):
+        message.from_buffer(b"\x09\x00\x07")
+
+    value = lenient.from_buffer(b"\x09\x01\x02\x03")
+    assert value.payload == b"\x01\x02\x03"
+
+    value.payload = b"\x04"
+    assert bytes(value) == b"\x09\x04"
+
+
+def test_assign_payload_sets_tag():
+    value = message.from_buffer(b"\x01\x00\x07")
+
+    payload = reading()
+    payload.sensor = 5
+    payload.value = 2.0
+    value.payload = payload
+    assert value.kind == u8(2)
+    assert bytes(value) == b"\x02\x05\x40\x00\x00\x00"
+
+    with pytest.raises(TypeError):
+        value.payload = u32(1)
+
+
+def test_assign_tag_resets_payload():
+    value = message.from_buffer(b"\x02\x03\x3f\x80\x00\x00")
+
+    value.kind = 1
+    assert bytes(value) == b"\x01\x00\x00"
+
+    value.payload.sequence = 9
+    value.kind = 1
+    assert value.payload.sequence == u16(9)
+
+
+def test_tagged_types_are_cached():
+    assert tagged["kind", {1: ping, 2: reading}] is tagged["kind", {1: ping, 2: reading}]
+    assert tagged["kind", {1: ping}] is not tagged["kind", {1: ping}, bytes]
+
+    class other(cstruct):
+        _CFIELDS = {"value": u32}
+
+    other.__name__ = "ping"
+    assert tagged["kind", {1: ping}] is not tagged["kind", {1: other}]
+
+
+def test_invalid_tagged():
+    with pytest.raises(LayoutError):
+        tagged["kind", {1: u8}]
+    with pytest.raises(LayoutError):
+        tagged["kind", {"a": ping}]
+
+    class undeclared(cstruct):
+        _CFIELDS = {"payload": tagged["kind", {1: ping}], "kind": u8}
+
+    with pytest.raises(LayoutError):
+        undeclared.__layout__()
+
+    with pytest.raises(LayoutError):
+        message.__packed_size__()
+
+
+def test_declare_tagged():
+    class declared:
+        kind: cerialize.u8
+        payload: tagged["kind", {1: ping, 2: reading}]
+
+    declared = cerialize.cstruct(declared, endianness=BigEndian, packed=True)
+    value = declared.from_buffer(b"\x01\x00\x07")
+    assert value.payload.sequence == u16(7)
+
+
+def test_assign_tag_with_declared_variants():
+    @cerialize.cstruct(endianness=BigEndian)
+    class start:
+        delay: cerialize.u16
+
+    @cerialize.cstruct(endianness=BigEndian, packed=True)
+    class command:
+        kind: cerialize.u8
+        payload: tagged["kind", {1: ping, 2: start}]
+
+    value = command.from_buffer(b"\x01\x00\x07")
+    value.kind = 2
+    assert value.payload.delay == u16(0)
+    assert bytes(value) == b"\x02\x00\x00"
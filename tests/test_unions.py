@@ -0,0 +1,69 @@
+import pytest
+import cerialize
+from _cerialize import cstruct, cunion, f32, u8, u16, u32, BigEndian, LittleEndian
+from cerialize.errors import LayoutError
+
+
+class number(cunion):
+    _CFIELDS = {"integer": u32, "real": f32, "raw": u8[4]}
+
+
+def test_union_layout():
+    assert number.__packed_size__() == 4
+    assert number.__alignment__() == 4
+    assert number.__layout__().fields == [("integer", u32, 0, 4), ("real", f32, 0, 4), ("raw", u8[4], 0, 4)]
+
+    class mixed(cunion):
+        _CFIELDS = {"small": u8, "large": u8[5], "aligned": u16}
+
+    assert mixed.__packed_size__() == 6
+    assert mixed.__alignment__() == 2
+
+
+def test_members_share_storage():
+    value = number.from_buffer(b"\x00\x00\x80\x3f", LittleEndian)
+    assert value.real == f32(1.0)
+    assert value.integer == u32(0x3F800000)
+    assert value.raw[3] == u8(0x3F)
+
+    value.integer = 0x40000000
+    assert value.real == f32(2.0)
+    assert bytes(value) == b"\x00\x00\x00\x40"
+
+
+def test_nested_union():
+    class packet(cstruct):
+        _CFIELDS = {"tag": u8, "value": number, "end": u8}
+        _ENDIANNESS = BigEndian
+
+    assert packet.__layout__().fields == [("tag", u8, 0, 1), ("value", number, 4, 4), ("end", u8, 8, 1)]
+
+    value = packet.from_buffer(b"\x01\x00\x00\x00\x3f\x80\x00\x00\x02\x00\x00\x00")
+    assert value.value.real == f32(1.0)
+
+    value.value.integer = 7
+    assert bytes(value)[4:8] == b"\x00\x00\x00\x07"
+
+
+def test_dynamic_member():
+    from _cerialize import cstring
+
+    class invalid(cunion):
+        _CFIELDS = {"text": cstring, "number": u32}
+
+    with pytest.raises(LayoutError):
+        invalid.__layout__()
+
+
+def test_declare_union():
+    @cerialize.cunion
+    class value:
+        integer: cerialize.u32
+        raw: cerialize.u8[4]
+
+    assert issubclass(value, cunion)
+    assert value.__packed_size__() == 4
+    assert bytes(value(integer=1)) == bytes(cerialize.u32(1))
+
+    with pytest.raises(TypeError):
+        value(real=1.0)
@@ -0,0 +1,42 @@
+from _cerialize import cstruct, u8, u16, u32, LittleEndian
+
+
+class inner(cstruct):
+    _CFIELDS = {"a": u16, "b": u16}
+
+
+class outer(cstruct):
+    _CFIELDS = {"tag": u32, "first": inner, "second": inner}
+
+
+def test_nested_struct_is_a_view():
+    value = outer.from_buffer(bytes(range(12)), LittleEndian)
+
+    assert value.first.a == u16(0x0504)
+    assert value.second.b == u16(0x0B0A)
+
+
+def test_write_through_child_is_visible_in_parent():
+    value = outer()
+    child = value.second
+    child.b = 0xBEEF
+
+    assert value.second.b == u16(0xBEEF)
+    assert value.first.b == u16(0)
+
+
+def test_children_share_the_same_memory():
+    value = outer()
+    first = value.first
+    again = value.first
+    first.a = 7
+
+    assert again.a == u16(7)
+
+
+def test_standalone_values_own_their_memory():
+    left = inner()
+    right = inner()
+    left.a = 1
+
+    assert right.a == u16(0)